@@ -9,6 +9,7 @@ use actix_web::HttpServer;
 use schemars::JsonSchema;
 use serde::Deserialize;
 use serde::Serialize;
+use swaggapi::security::SecurityScheme;
 use swaggapi::utils::SchemalessJson;
 use swaggapi::ApiContext;
 use swaggapi::SwaggapiPage;
@@ -55,7 +56,8 @@ pub async fn schemaless_json(json2: SchemalessJson<()>) -> SchemalessJson<()> {
 #[page(
     title = "My application",
     description = "This is the first revision of my application's api",
-    filename = "openapi_v1.json"
+    filename = "openapi_v1.json",
+    security_schemes = &[("bearer", SecurityScheme::bearer())]
 )]
 pub struct ApiV1;
 
@@ -47,7 +47,7 @@ pub async fn update_resource(path: Path<PathUuid>, json: Json<Resource>) -> Json
     }))
 }
 
-#[delete("/resource/{uuid}")]
+#[delete("/resource/{uuid}", security("bearer"))]
 pub async fn delete_resource(path: Path<PathUuid>) -> Json<bool> {
     Json(modify(move |map| map.remove(&path.uuid).is_some()))
 }
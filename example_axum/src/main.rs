@@ -2,11 +2,13 @@ mod rest;
 
 use std::error::Error;
 
+use axum::Form;
 use axum::Json;
 use axum::Router;
 use schemars::JsonSchema;
 use serde::Deserialize;
 use serde::Serialize;
+use swaggapi::security::SecurityScheme;
 use swaggapi::utils::SchemalessJson;
 use swaggapi::ApiContext;
 use swaggapi::SwaggapiPage;
@@ -22,12 +24,10 @@ pub async fn index() -> &'static str {
 #[derive(Deserialize, JsonSchema)]
 pub struct SubmitForm {}
 
-/*
 #[swaggapi::get("/submit")]
 pub async fn submit(_form: Form<SubmitForm>) -> Vec<u8> {
     Vec::new()
 }
-*/
 
 /// here be dragons
 #[derive(Deserialize, Serialize, JsonSchema)]
@@ -56,7 +56,8 @@ pub async fn schemaless_json(json2: SchemalessJson<()>) -> SchemalessJson<()> {
 #[page(
     title = "My application",
     description = "This is the first revision of my application's api",
-    filename = "openapi_v1.json"
+    filename = "openapi_v1.json",
+    security_schemes = &[("bearer", SecurityScheme::bearer())]
 )]
 pub struct ApiV1;
 
@@ -80,6 +81,7 @@ async fn main() -> Result<(), Box<dyn Error>> {
                     "/api/v1",
                     ApiContext::new()
                         .page(ApiV1)
+                        .handler(submit)
                         .handler(index)
                         .handler(schemaless_json)
                         .nest(
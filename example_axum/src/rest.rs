@@ -32,12 +32,12 @@ pub async fn create_resource(json: Json<Resource>) -> Json<Uuid> {
     Json(uuid)
 }
 
-#[get("/resource/:uuid")]
+#[get("/resource/{uuid}")]
 pub async fn get_resource(path: Path<Uuid>) -> Json<Option<Resource>> {
     Json(modify(move |map| map.get(&path).cloned()))
 }
 
-#[put("/resource/:uuid")]
+#[put("/resource/{uuid}")]
 pub async fn update_resource(path: Path<PathUuid>, json: Json<Resource>) -> Json<bool> {
     Json(modify(move |map| {
         map.contains_key(&path.uuid) && {
@@ -47,7 +47,7 @@ pub async fn update_resource(path: Path<PathUuid>, json: Json<Resource>) -> Json
     }))
 }
 
-#[delete("/resource/:uuid")]
+#[delete("/resource/{uuid}", security("bearer"))]
 pub async fn delete_resource(path: Path<PathUuid>) -> Json<bool> {
     Json(modify(move |map| map.remove(&path.uuid).is_some()))
 }
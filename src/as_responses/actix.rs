@@ -11,6 +11,7 @@ use openapiv3::StatusCode;
 use schemars::JsonSchema;
 use serde::Serialize;
 
+use crate::as_responses::merge_responses;
 use crate::as_responses::ok_binary;
 use crate::as_responses::ok_json;
 use crate::as_responses::ok_text;
@@ -47,22 +48,9 @@ impl<T: Serialize + JsonSchema> AsResponses for web::Json<T> {
 
 impl<T: AsResponses, E: AsResponses> AsResponses for Result<T, E> {
     fn responses(gen: &mut SchemaGenerator) -> Responses {
-        let ok = T::responses(gen);
-        let err = E::responses(gen);
-
-        Responses {
-            default: ok.default.or(err.default),
-            responses: ok
-                .responses
-                .into_iter()
-                .chain(err.responses.into_iter())
-                .collect(),
-            extensions: ok
-                .extensions
-                .into_iter()
-                .chain(err.extensions.into_iter())
-                .collect(),
-        }
+        let mut responses = T::responses(gen);
+        merge_responses(&mut responses, E::responses(gen));
+        responses
     }
 }
 
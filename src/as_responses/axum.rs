@@ -1,5 +1,7 @@
 use std::borrow::Cow;
 
+use axum::http::HeaderMap;
+use axum::response::AppendHeaders;
 use axum::response::{Html, Redirect};
 use axum::Json;
 use bytes::buf::Chain;
@@ -16,9 +18,12 @@ use openapiv3::StatusCode;
 use schemars::JsonSchema;
 use serde::Serialize;
 
+use crate::as_responses::merge_responses;
 use crate::as_responses::ok_empty;
 use crate::as_responses::ok_json;
 use crate::as_responses::ok_text;
+use crate::as_responses::unknown_status;
+use crate::as_responses::AsResponseParts;
 use crate::as_responses::AsResponses;
 use crate::as_responses::{ok_binary, ok_html};
 use crate::internals::SchemaGenerator;
@@ -113,18 +118,9 @@ where
     E: AsResponses,
 {
     fn responses(gen: &mut SchemaGenerator) -> Responses {
-        let mut res = E::responses(gen);
-        let ok_res = T::responses(gen);
-
-        // As we want to preserve in almost any cases the Ok branch of the result, we're extending
-        // the IndexMaps of the error-branch with those of the ok-branch
-        res.responses.extend(ok_res.responses);
-        res.extensions.extend(ok_res.extensions);
-        if ok_res.default.is_some() {
-            res.default = ok_res.default;
-        }
-
-        res
+        let mut responses = T::responses(gen);
+        merge_responses(&mut responses, E::responses(gen));
+        responses
     }
 }
 
@@ -171,3 +167,79 @@ impl<T> AsResponses for Html<T> {
         ok_html()
     }
 }
+
+impl AsResponses for axum::http::StatusCode {
+    fn responses(_gen: &mut SchemaGenerator) -> Responses {
+        unknown_status(ok_empty())
+    }
+}
+
+impl AsResponseParts for HeaderMap {}
+
+impl<I> AsResponseParts for AppendHeaders<I> {}
+
+impl<K, V, const N: usize> AsResponseParts for [(K, V); N] {}
+
+#[cfg(feature = "axum-extra")]
+impl<H: axum_extra::headers::Header> AsResponseParts for axum_extra::TypedHeader<H> {
+    fn response_parts(gen: &mut SchemaGenerator, responses: &mut Responses) {
+        for response in responses
+            .responses
+            .values_mut()
+            .chain(responses.default.as_mut())
+        {
+            if let ReferenceOr::Item(response) = response {
+                response.headers.insert(
+                    H::name().to_string(),
+                    ReferenceOr::Item(Header {
+                        description: None,
+                        style: Default::default(),
+                        required: true,
+                        deprecated: None,
+                        format: ParameterSchemaOrContent::Schema(gen.generate::<String>()),
+                        example: None,
+                        examples: Default::default(),
+                        extensions: Default::default(),
+                    }),
+                );
+            }
+        }
+    }
+}
+
+/// Implements [`AsResponses`] for tuples of [`AsResponseParts`] followed by an [`AsResponses`]
+///
+/// A leading [`StatusCode`](axum::http::StatusCode) overwrites the status code at runtime,
+/// so the responses are described as `default`.
+macro_rules! impl_as_responses_for_tuple {
+    ($($part:ident),*) => {
+        impl<$($part: AsResponseParts,)* R: AsResponses> AsResponses for ($($part,)* R,) {
+            fn responses(gen: &mut SchemaGenerator) -> Responses {
+                #[allow(unused_mut)]
+                let mut responses = R::responses(gen);
+                $($part::response_parts(gen, &mut responses);)*
+                responses
+            }
+        }
+
+        impl<$($part: AsResponseParts,)* R: AsResponses> AsResponses
+            for (axum::http::StatusCode, $($part,)* R,)
+        {
+            fn responses(gen: &mut SchemaGenerator) -> Responses {
+                #[allow(unused_mut)]
+                let mut responses = unknown_status(R::responses(gen));
+                $($part::response_parts(gen, &mut responses);)*
+                responses
+            }
+        }
+    };
+}
+impl_as_responses_for_tuple!();
+impl_as_responses_for_tuple!(T1);
+impl_as_responses_for_tuple!(T1, T2);
+impl_as_responses_for_tuple!(T1, T2, T3);
+impl_as_responses_for_tuple!(T1, T2, T3, T4);
+impl_as_responses_for_tuple!(T1, T2, T3, T4, T5);
+impl_as_responses_for_tuple!(T1, T2, T3, T4, T5, T6);
+impl_as_responses_for_tuple!(T1, T2, T3, T4, T5, T6, T7);
+impl_as_responses_for_tuple!(T1, T2, T3, T4, T5, T6, T7, T8);
@@ -6,6 +6,8 @@ use openapiv3::MediaType;
 use openapiv3::ReferenceOr;
 use openapiv3::Response;
 use openapiv3::Responses;
+use openapiv3::Schema;
+use openapiv3::SchemaKind;
 use openapiv3::StatusCode;
 use schemars::JsonSchema;
 
@@ -27,6 +29,17 @@ pub trait AsResponses {
     fn responses(gen: &mut SchemaGenerator) -> Responses;
 }
 
+/// A type which modifies a response when returned in a tuple before a type implementing [`AsResponses`]
+///
+/// This type should be implemented by everything which implements
+/// [`IntoResponseParts`](::axum::response::IntoResponseParts) when using [axum](::axum).
+pub trait AsResponseParts {
+    /// Modify the [responses object](https://spec.openapis.org/oas/v3.0.3#responses-object) of the tuple's last element
+    ///
+    /// The default implementation leaves it unchanged.
+    fn response_parts(_gen: &mut SchemaGenerator, _responses: &mut Responses) {}
+}
+
 /// Helper function for building a [`Responses`] for some simple responses
 pub fn simple_responses(responses: impl IntoIterator<Item = SimpleResponse>) -> Responses {
     Responses {
@@ -50,6 +63,147 @@ pub fn simple_responses(responses: impl IntoIterator<Item = SimpleResponse>) ->
     }
 }
 
+/// Helper function for adding `other`'s responses to `responses`
+///
+/// Responses sharing a status code are merged:
+/// their descriptions are concatenated, their content types are combined
+/// and differing schemas for the same content type are joined using `oneOf`.
+///
+/// Responses which are references can't be merged and `other`'s are ignored.
+pub fn merge_responses(responses: &mut Responses, other: Responses) {
+    for (status_code, other) in other.responses {
+        match responses.responses.get_mut(&status_code) {
+            None => {
+                responses.responses.insert(status_code, other);
+            }
+            Some(ReferenceOr::Item(response)) => {
+                if let ReferenceOr::Item(other) = other {
+                    merge_response(response, other);
+                }
+            }
+            Some(ReferenceOr::Reference { .. }) => {}
+        }
+    }
+    match (&mut responses.default, other.default) {
+        (Some(ReferenceOr::Item(response)), Some(ReferenceOr::Item(other))) => {
+            merge_response(response, other)
+        }
+        (default @ None, other) => *default = other,
+        _ => {}
+    }
+    responses.extensions.extend(other.extensions);
+}
+
+/// Merges `other` into `response`
+fn merge_response(response: &mut Response, other: Response) {
+    if response.description.is_empty() {
+        response.description = other.description;
+    } else if !other.description.is_empty()
+        && !response
+            .description
+            .split("\n\n")
+            .any(|paragraph| paragraph == other.description)
+    {
+        response.description = format!("{}\n\n{}", response.description, other.description);
+    }
+    for (name, header) in other.headers {
+        response.headers.entry(name).or_insert(header);
+    }
+    for (mime_type, other) in other.content {
+        let Some(media_type) = response.content.get_mut(&mime_type) else {
+            response.content.insert(mime_type, other);
+            continue;
+        };
+        media_type.schema = match (media_type.schema.take(), other.schema) {
+            (Some(schema), Some(other)) => Some(one_of(schema, other)),
+            (schema, other) => schema.or(other),
+        };
+    }
+    for (name, link) in other.links {
+        response.links.entry(name).or_insert(link);
+    }
+    response.extensions.extend(other.extensions);
+}
+
+/// Joins two schemas using `oneOf` unless they are equal
+fn one_of(schema: ReferenceOr<Schema>, other: ReferenceOr<Schema>) -> ReferenceOr<Schema> {
+    if schema == other {
+        return schema;
+    }
+    let mut one_of = match schema {
+        ReferenceOr::Item(Schema {
+            schema_data,
+            schema_kind: SchemaKind::OneOf { one_of },
+        }) if schema_data == Default::default() => one_of,
+        schema => vec![schema],
+    };
+    if !one_of.contains(&other) {
+        one_of.push(other);
+    }
+    ReferenceOr::Item(Schema {
+        schema_data: Default::default(),
+        schema_kind: SchemaKind::OneOf { one_of },
+    })
+}
+
+/// Helper function for describing `responses` which are sent with a status code only known at runtime
+///
+/// All responses are merged into the `default` response.
+pub fn unknown_status(responses: Responses) -> Responses {
+    Responses {
+        default: collapse_responses(responses.responses.into_values().chain(responses.default)),
+        responses: IndexMap::new(),
+        extensions: responses.extensions,
+    }
+}
+
+/// Helper function for describing `responses` which are sent with a statically known status code
+///
+/// All responses are merged into a single one for `status_code`.
+pub fn with_status(responses: Responses, status_code: StatusCode) -> Responses {
+    Responses {
+        responses: collapse_responses(responses.responses.into_values().chain(responses.default))
+            .map(|response| (status_code, response))
+            .into_iter()
+            .collect(),
+        default: None,
+        extensions: responses.extensions,
+    }
+}
+
+/// Merges several responses into a single one
+fn collapse_responses(
+    responses: impl IntoIterator<Item = ReferenceOr<Response>>,
+) -> Option<ReferenceOr<Response>> {
+    let mut collapsed = None;
+    for response in responses {
+        match collapsed {
+            None => collapsed = Some(response),
+            Some(ReferenceOr::Item(ref mut collapsed)) => {
+                if let ReferenceOr::Item(response) = response {
+                    merge_response(collapsed, response);
+                }
+            }
+            Some(ReferenceOr::Reference { .. }) => {}
+        }
+    }
+    collapsed
+}
+
+/// Helper function for building a [`Responses`] for a single response without a body
+pub fn empty_response(status_code: StatusCode, description: String) -> Responses {
+    Responses {
+        responses: IndexMap::from([(
+            status_code,
+            ReferenceOr::Item(Response {
+                description,
+                ..Default::default()
+            }),
+        )]),
+        ..Default::default()
+    }
+}
+
 /// Describes the response for a specific status code
 pub struct SimpleResponse {
     /// The response's status code
@@ -110,6 +264,34 @@ pub fn ok_empty() -> Responses {
     }
 }
 
+/// Helper function for building a [`Responses`] for an empty `401` response
+pub fn unauthorized() -> Responses {
+    Responses {
+        responses: IndexMap::from([(
+            StatusCode::Code(401),
+            ReferenceOr::Item(Response {
+                description: "Unauthorized".to_string(),
+                ..Default::default()
+            }),
+        )]),
+        ..Default::default()
+    }
+}
+
+/// Helper function for building a [`Responses`] for an empty `403` response
+pub fn forbidden() -> Responses {
+    Responses {
+        responses: IndexMap::from([(
+            StatusCode::Code(403),
+            ReferenceOr::Item(Response {
+                description: "Forbidden".to_string(),
+                ..Default::default()
+            }),
+        )]),
+        ..Default::default()
+    }
+}
+
 /// Helper function for building a [`Responses`] for a simple `200` json response using a schema
 pub fn ok_json<T: JsonSchema>(gen: &mut SchemaGenerator) -> Responses {
     simple_responses([
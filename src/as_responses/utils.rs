@@ -6,10 +6,15 @@ use openapiv3::SchemaKind;
 use openapiv3::StatusCode;
 use serde::Serialize;
 
+use crate::as_responses::empty_response;
 use crate::as_responses::simple_responses;
+use crate::as_responses::with_status;
 use crate::as_responses::AsResponses;
 use crate::as_responses::SimpleResponse;
 use crate::internals::SchemaGenerator;
+use crate::utils::Accepted;
+use crate::utils::Created;
+use crate::utils::NoContent;
 use crate::utils::SchemalessJson;
 
 impl<T: Serialize> AsResponses for SchemalessJson<T> {
@@ -31,3 +36,21 @@ impl<T: Serialize> AsResponses for SchemalessJson<T> {
         ])
     }
 }
+
+impl<T: AsResponses> AsResponses for Created<T> {
+    fn responses(gen: &mut SchemaGenerator) -> Responses {
+        with_status(T::responses(gen), StatusCode::Code(201))
+    }
+}
+
+impl<T: AsResponses> AsResponses for Accepted<T> {
+    fn responses(gen: &mut SchemaGenerator) -> Responses {
+        with_status(T::responses(gen), StatusCode::Code(202))
+    }
+}
+
+impl AsResponses for NoContent {
+    fn responses(_gen: &mut SchemaGenerator) -> Responses {
+        empty_response(StatusCode::Code(204), "No content".to_string())
+    }
+}
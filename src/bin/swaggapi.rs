@@ -0,0 +1,66 @@
+//! Runs [`swaggapi::cli`] on an OpenAPI document produced by an application hook or stored in a file
+//!
+//! Usage: `swaggapi --exec <HOOK> <COMMAND>` or `swaggapi <FILE> <COMMAND>`
+
+use std::env;
+use std::process::Command;
+use std::process::ExitCode;
+
+const USAGE: &str = "\
+Usage: swaggapi --exec <HOOK> <COMMAND>
+       swaggapi <FILE> <COMMAND>
+
+HOOK is a command line (split at whitespace) printing the document to stdout,
+for example \"cargo run --bin openapi -- export\".
+
+Run `swaggapi <FILE> help` for a list of commands";
+
+fn main() -> ExitCode {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let (openapi, command) = match args.as_slice() {
+        [flag] if flag == "--help" || flag == "-h" => {
+            println!("{USAGE}");
+            return ExitCode::SUCCESS;
+        }
+        [flag, hook, command @ ..] if flag == "--exec" && !command.is_empty() => {
+            (exec_hook(hook), command)
+        }
+        [file, command @ ..] if file != "--exec" && !command.is_empty() => {
+            (swaggapi::cli::read_spec(file), command)
+        }
+        _ => {
+            eprintln!("{USAGE}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    match openapi {
+        Ok(openapi) => swaggapi::cli::run_with_args(&openapi, command.iter().cloned()),
+        Err(error) => {
+            eprintln!("{error}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+/// Runs an application hook and parses the document it prints
+fn exec_hook(hook: &str) -> Result<serde_json::Value, String> {
+    let mut words = hook.split_whitespace();
+    let program = words.next().ok_or("The hook is empty")?;
+    let output = Command::new(program)
+        .args(words)
+        .output()
+        .map_err(|error| format!("Couldn't run `{hook}`: {error}"))?;
+    if !output.status.success() {
+        return Err(format!(
+            "`{hook}` failed with {}:\n{}",
+            output.status,
+            String::from_utf8_lossy(&output.stderr)
+        ));
+    }
+    let stdout =
+        String::from_utf8(output.stdout).map_err(|_| format!("`{hook}` printed invalid UTF-8"))?;
+    // The hook may export YAML as well, which is a superset of JSON
+    swaggapi::cli::parse_spec(&stdout, !stdout.trim_start().starts_with('{'))
+        .map_err(|error| format!("Couldn't parse the output of `{hook}`: {error}"))
+}
@@ -0,0 +1,674 @@
+//! Command line interface for exporting, validating and diffing OpenAPI documents
+//!
+//! It is meant to be used in CI to catch broken documents or unintended changes to an api.
+//! Both OpenAPI 3.0 and 3.1 documents are supported.
+//!
+//! The document is provided by your application through a small binary calling [`run`]:
+//!
+//! ```rust,no_run
+//! # use std::process::ExitCode;
+//! # use swaggapi::{PageOfEverything, SwaggapiPage};
+//! // src/bin/openapi.rs
+//! fn main() -> ExitCode {
+//!     // Build your `ApiContext`s (see `ApiContext::build_page`) or the application itself
+//!     // to populate the page you want to check
+//!     swaggapi::cli::run(&*PageOfEverything.openapi_json())
+//! }
+//! ```
+//!
+//! Alternatively, the `swaggapi` binary (installed with the `cli` feature)
+//! loads the document from such an application hook or from a file:
+//!
+//! - `swaggapi --exec <HOOK> <COMMAND>` runs the command line `HOOK`
+//!   (split at whitespace, for example `"cargo run --bin openapi -- export"`)
+//!   and reads the document from its stdout
+//! - `swaggapi <FILE> <COMMAND>` reads the document from `FILE`
+//!
+//! ## Commands
+//! - `export [--yaml] [FILE]`: Writes the document to `FILE` or stdout
+//!
+//!     YAML is used if `--yaml` is given or `FILE` ends with `.yaml` or `.yml`.
+//! - `validate`: Checks the document for [`ValidationError`]s
+//! - `diff FILE`: Compares the document with an older one stored in `FILE` and lists the [`SpecChange`]s
+//! - `breaking FILE`: Compares the operations with an older document stored in `FILE`
+//!   and lists the [`ApiChange`](crate::compatibility::ApiChange)s
+//!
+//! `validate` and `diff` exit with a failure if they find anything,
+//! `breaking` only if it finds a breaking change.
+
+use std::collections::HashSet;
+use std::env;
+use std::fmt;
+use std::fs;
+use std::path::Path;
+use std::process::ExitCode;
+
+use indexmap::IndexMap;
+use regex::Regex;
+use serde::Serialize;
+use serde_json::Map;
+use serde_json::Value;
+
+use crate::compatibility;
+use crate::internals::walk::escape;
+use crate::internals::walk::object;
+use crate::internals::walk::resolve;
+use crate::internals::walk::walk_document;
+use crate::internals::walk::METHODS;
+
+/// Runs the command line interface on `openapi` using the process' arguments
+///
+/// `openapi` is either an [`openapiv3::OpenAPI`]
+/// or a [`serde_json::Value`] (like the one returned by [`SwaggapiPage::openapi_json`](crate::SwaggapiPage::openapi_json)).
+///
+/// The first argument (the program's name) is skipped.
+pub fn run(openapi: &impl Serialize) -> ExitCode {
+    run_with_args(openapi, env::args().skip(1))
+}
+
+/// Runs the command line interface on `openapi` using the given arguments
+///
+/// Errors and results are printed to stderr and stdout respectively.
+pub fn run_with_args(openapi: &impl Serialize, args: impl IntoIterator<Item = String>) -> ExitCode {
+    let args: Vec<String> = args.into_iter().collect();
+    let args: Vec<&str> = args.iter().map(String::as_str).collect();
+    let result = serde_json::to_value(openapi)
+        .map_err(|error| format!("Couldn't serialize the document: {error}"))
+        .and_then(|openapi| execute(&openapi, &args));
+    match result {
+        Ok(true) => ExitCode::SUCCESS,
+        Ok(false) => ExitCode::FAILURE,
+        Err(error) => {
+            eprintln!("{error}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+/// Executes a command
+///
+/// Returns whether the command succeeded.
+fn execute(openapi: &Value, args: &[&str]) -> Result<bool, String> {
+    match args {
+        ["export", rest @ ..] => export(openapi, rest),
+        ["validate"] => {
+            let errors = validate(openapi);
+            for error in &errors {
+                println!("{error}");
+            }
+            Ok(errors.is_empty())
+        }
+        ["diff", file] => read_spec(file).map(|old| {
+            let changes = diff(&old, openapi);
+            for change in &changes {
+                println!("{change}");
+            }
+            changes.is_empty()
+        }),
+        ["breaking", file] => read_spec(file).map(|old| {
+            let changes = compatibility::compare_json(&old, openapi);
+            for change in &changes {
+                println!("{change}");
+            }
+            !changes.iter().any(|change| change.breaking)
+        }),
+        ["help" | "--help" | "-h"] => {
+            println!("{USAGE}");
+            Ok(true)
+        }
+        _ => Err(format!("Invalid arguments\n\n{USAGE}")),
+    }
+}
+
+const USAGE: &str = "\
+Commands:
+    export [--yaml] [FILE]  Write the document to FILE or stdout
+    validate                Check the document for broken references, path parameters and operationIds
+    diff FILE               List the changes since the document stored in FILE
+    breaking FILE           Classify the changes since the document stored in FILE as breaking or non-breaking
+    help                    Print this message";
+
+/// Handles the `export` command
+fn export(openapi: &Value, args: &[&str]) -> Result<bool, String> {
+    let (yaml, file) = match args {
+        [] => (false, None),
+        ["--yaml"] => (true, None),
+        [file] => (is_yaml(file), Some(*file)),
+        ["--yaml", file] | [file, "--yaml"] => (true, Some(*file)),
+        _ => return Err(format!("Invalid arguments\n\n{USAGE}")),
+    };
+
+    let output = if yaml {
+        serde_yaml::to_string(openapi).map_err(|error| error.to_string())?
+    } else {
+        serde_json::to_string_pretty(openapi).map_err(|error| error.to_string())?
+    };
+    match file {
+        Some(file) => {
+            fs::write(file, output).map_err(|error| format!("Couldn't write `{file}`: {error}"))?
+        }
+        None => println!("{output}"),
+    }
+    Ok(true)
+}
+
+/// Reads an OpenAPI document from a file
+///
+/// The file is parsed as YAML if it ends with `.yaml` or `.yml` and as JSON otherwise.
+pub fn read_spec(path: impl AsRef<Path>) -> Result<Value, String> {
+    let path = path.as_ref();
+    let content = fs::read_to_string(path)
+        .map_err(|error| format!("Couldn't read `{}`: {error}", path.display()))?;
+    parse_spec(&content, is_yaml(path))
+        .map_err(|error| format!("Couldn't parse `{}`: {error}", path.display()))
+}
+
+/// Parses an OpenAPI document from JSON or YAML
+///
+/// Only checks the document to be an object containing an `openapi` version.
+pub fn parse_spec(content: &str, yaml: bool) -> Result<Value, String> {
+    let document: Value = if yaml {
+        serde_yaml::from_str(content).map_err(|error| error.to_string())?
+    } else {
+        serde_json::from_str(content).map_err(|error| error.to_string())?
+    };
+    match document["openapi"].as_str() {
+        Some(version) if version.starts_with("3.") => Ok(document),
+        Some(version) => Err(format!("Unsupported OpenAPI version `{version}`")),
+        None => Err("Missing the `openapi` version".to_string()),
+    }
+}
+
+fn is_yaml(path: impl AsRef<Path>) -> bool {
+    matches!(
+        path.as_ref().extension().and_then(|ext| ext.to_str()),
+        Some("yaml" | "yml")
+    )
+}
+
+/// A structural problem in an OpenAPI document
+#[derive(Clone, Eq, PartialEq, Hash, Debug)]
+pub struct ValidationError {
+    /// [JSON pointer](https://datatracker.ietf.org/doc/html/rfc6901) to the offending object
+    pub pointer: String,
+
+    /// What went wrong
+    pub kind: ValidationErrorKind,
+}
+
+/// The different kinds of [`ValidationError`]
+#[derive(Clone, Eq, PartialEq, Hash, Debug)]
+#[non_exhaustive]
+pub enum ValidationErrorKind {
+    /// A `$ref` points to something which doesn't exist in the document
+    ///
+    /// References to other documents are not checked.
+    UnresolvedReference(String),
+
+    /// An operation's path contains a parameter which isn't declared
+    UndeclaredPathParameter(String),
+
+    /// An operation declares a path parameter which its path doesn't contain
+    UnknownPathParameter(String),
+
+    /// Several operations share the same `operationId`
+    DuplicateOperationId(String),
+}
+
+impl fmt::Display for ValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "#{}: {}", self.pointer, self.kind)
+    }
+}
+
+impl fmt::Display for ValidationErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnresolvedReference(reference) => write!(f, "Can't resolve `{reference}`"),
+            Self::UndeclaredPathParameter(name) => {
+                write!(f, "Path parameter `{name}` is not declared")
+            }
+            Self::UnknownPathParameter(name) => {
+                write!(f, "Path parameter `{name}` is not part of the path")
+            }
+            Self::DuplicateOperationId(id) => {
+                write!(f, "operationId `{id}` is used by several operations")
+            }
+        }
+    }
+}
+
+/// Checks an OpenAPI document for structural problems
+///
+/// - every local `$ref` can be resolved
+///   (references inside payloads like examples or default values are ignored)
+/// - every operation declares exactly the path parameters its path contains
+/// - every `operationId` is unique
+pub fn validate(openapi: &Value) -> Vec<ValidationError> {
+    let mut errors = Vec::new();
+
+    walk_document(openapi, &mut |_, pointer, value| {
+        let Some(reference) = value.get("$ref").and_then(Value::as_str) else {
+            return;
+        };
+        if let Some(target) = reference.strip_prefix('#') {
+            if openapi.pointer(target).is_none() {
+                errors.push(ValidationError {
+                    pointer: pointer.to_string(),
+                    kind: ValidationErrorKind::UnresolvedReference(reference.to_string()),
+                });
+            }
+        }
+    });
+
+    let template = Regex::new(r"\{([^}]+)}").unwrap();
+    let mut operation_ids = HashSet::new();
+    for (path, item) in object(&openapi["paths"]) {
+        let in_path: HashSet<&str> = template
+            .captures_iter(path)
+            .map(|captures| captures.get(1).unwrap().as_str())
+            .collect();
+
+        for (method, operation) in methods(item) {
+            let pointer = format!("/paths/{}/{method}", escape(path));
+
+            let declared: HashSet<&str> = [item, operation]
+                .into_iter()
+                .filter_map(|x| x["parameters"].as_array())
+                .flatten()
+                .map(|parameter| resolve(openapi, parameter))
+                .filter(|parameter| parameter["in"] == "path")
+                .filter_map(|parameter| parameter["name"].as_str())
+                .collect();
+            for name in in_path.difference(&declared) {
+                errors.push(ValidationError {
+                    pointer: pointer.clone(),
+                    kind: ValidationErrorKind::UndeclaredPathParameter(name.to_string()),
+                });
+            }
+            for name in declared.difference(&in_path) {
+                errors.push(ValidationError {
+                    pointer: pointer.clone(),
+                    kind: ValidationErrorKind::UnknownPathParameter(name.to_string()),
+                });
+            }
+
+            if let Some(operation_id) = operation["operationId"].as_str() {
+                if !operation_ids.insert(operation_id) {
+                    errors.push(ValidationError {
+                        pointer,
+                        kind: ValidationErrorKind::DuplicateOperationId(operation_id.to_string()),
+                    });
+                }
+            }
+        }
+    }
+
+    errors
+}
+
+/// Iterates over the operations of a path item
+fn methods(item: &Value) -> impl Iterator<Item = (&'static str, &Value)> {
+    METHODS
+        .into_iter()
+        .filter_map(|method| Some((method, item.get(method)?)))
+}
+
+/// A difference between two OpenAPI documents
+#[derive(Clone, Eq, PartialEq, Hash, Debug)]
+pub struct SpecChange {
+    /// What happened to the item
+    pub kind: SpecChangeKind,
+
+    /// The changed item, for example ``GET /users`` or ``schema `User` ``
+    pub item: String,
+}
+
+/// The different kinds of [`SpecChange`]
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub enum SpecChangeKind {
+    /// The item only exists in the new document
+    Added,
+
+    /// The item only exists in the old document
+    Removed,
+
+    /// The item exists in both documents but differs
+    Changed,
+}
+
+impl fmt::Display for SpecChange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let kind = match self.kind {
+            SpecChangeKind::Added => "Added",
+            SpecChangeKind::Removed => "Removed",
+            SpecChangeKind::Changed => "Changed",
+        };
+        write!(f, "{kind} {}", self.item)
+    }
+}
+
+/// Compares two OpenAPI documents
+///
+/// The documents are compared by their operations (including webhooks), components,
+/// version, servers, security and tags.
+/// An operation is identified by its method and path (or webhook name).
+pub fn diff(old: &Value, new: &Value) -> Vec<SpecChange> {
+    let mut changes = Vec::new();
+
+    let old_operations = operations(old);
+    let new_operations = operations(new);
+    diff_maps(
+        &old_operations,
+        &new_operations,
+        |x| x.to_string(),
+        &mut changes,
+    );
+
+    for (field, name) in [
+        ("schemas", "schema"),
+        ("responses", "response"),
+        ("parameters", "parameter"),
+        ("examples", "example"),
+        ("requestBodies", "request body"),
+        ("headers", "header"),
+        ("securitySchemes", "security scheme"),
+        ("links", "link"),
+        ("callbacks", "callback"),
+        ("pathItems", "path item"),
+    ] {
+        diff_maps(
+            &to_index_map(object(&old["components"][field])),
+            &to_index_map(object(&new["components"][field])),
+            |key| format!("{name} `{key}`"),
+            &mut changes,
+        );
+    }
+
+    for item in ["openapi", "servers", "security", "tags"] {
+        if old[item] != new[item] {
+            changes.push(SpecChange {
+                kind: SpecChangeKind::Changed,
+                item: item.to_string(),
+            });
+        }
+    }
+
+    changes
+}
+
+/// Collects a document's operations as `METHOD /path` and its webhooks' ones as ``METHOD webhook `name` ``
+///
+/// Parameters declared on the path item are included in its operations.
+fn operations(openapi: &Value) -> IndexMap<String, Value> {
+    let mut operations = IndexMap::new();
+    let paths = object(&openapi["paths"])
+        .iter()
+        .map(|(path, item)| (path.clone(), item));
+    let webhooks = object(&openapi["webhooks"])
+        .iter()
+        .map(|(name, item)| (format!("webhook `{name}`"), item));
+    for (name, item) in paths.chain(webhooks) {
+        let item = resolve(openapi, item);
+        for (method, operation) in methods(item) {
+            let mut operation = operation.clone();
+            if let Some(Value::Array(parameters)) = item.get("parameters") {
+                let operation_parameters = operation["parameters"].take();
+                let mut all = parameters.clone();
+                all.extend(
+                    operation_parameters
+                        .as_array()
+                        .into_iter()
+                        .flatten()
+                        .cloned(),
+                );
+                operation["parameters"] = Value::Array(all);
+            }
+            operations.insert(format!("{} {name}", method.to_uppercase()), operation);
+        }
+    }
+    operations
+}
+
+fn to_index_map(map: &Map<String, Value>) -> IndexMap<String, &Value> {
+    map.iter()
+        .map(|(key, value)| (key.clone(), value))
+        .collect()
+}
+
+/// Compares two maps and pushes a [`SpecChange`] for every added, removed or changed entry
+fn diff_maps<T: PartialEq>(
+    old: &IndexMap<String, T>,
+    new: &IndexMap<String, T>,
+    item: impl Fn(&str) -> String,
+    changes: &mut Vec<SpecChange>,
+) {
+    let mut push = |kind, key: &str| {
+        changes.push(SpecChange {
+            kind,
+            item: item(key),
+        })
+    };
+    for (key, old_value) in old {
+        match new.get(key) {
+            None => push(SpecChangeKind::Removed, key),
+            Some(new_value) if new_value != old_value => push(SpecChangeKind::Changed, key),
+            Some(_) => {}
+        }
+    }
+    for key in new.keys() {
+        if !old.contains_key(key) {
+            push(SpecChangeKind::Added, key);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use serde_json::json;
+
+    use super::*;
+
+    /// Get a path in the temporary directory which is unique to the calling test
+    fn temp_file(name: &str) -> PathBuf {
+        let directory = env::temp_dir().join(format!("swaggapi-cli-{}", std::process::id()));
+        fs::create_dir_all(&directory).unwrap();
+        directory.join(name)
+    }
+
+    fn write_spec(name: &str, document: &Value) -> String {
+        let file = temp_file(name);
+        fs::write(&file, document.to_string()).unwrap();
+        file.to_str().unwrap().to_string()
+    }
+
+    fn document(paths: Value) -> Value {
+        json!({
+            "openapi": "3.0.3",
+            "info": { "title": "", "version": "" },
+            "paths": paths,
+            "components": { "schemas": { "User": { "type": "object" } } },
+        })
+    }
+
+    #[test]
+    fn export_writes_json_and_yaml() {
+        let openapi = document(json!({}));
+
+        let json_file = temp_file("export.json");
+        let yaml_file = temp_file("export.yaml");
+        let forced_yaml_file = temp_file("export.txt");
+        for args in [
+            ["export", json_file.to_str().unwrap()].as_slice(),
+            &["export", yaml_file.to_str().unwrap()],
+            &["export", "--yaml", forced_yaml_file.to_str().unwrap()],
+        ] {
+            assert_eq!(execute(&openapi, args), Ok(true));
+        }
+        assert_eq!(read_spec(&json_file), Ok(openapi.clone()));
+        assert_eq!(read_spec(&yaml_file), Ok(openapi.clone()));
+        let forced_yaml = fs::read_to_string(&forced_yaml_file).unwrap();
+        assert_eq!(parse_spec(&forced_yaml, true), Ok(openapi.clone()));
+
+        assert!(execute(&openapi, &["export", "a", "b"]).is_err());
+    }
+
+    #[test]
+    fn validate_reports_structural_problems() {
+        let openapi = document(json!({
+            "/users/{id}": {
+                "get": {
+                    "operationId": "get",
+                    "parameters": [{ "in": "path", "name": "name", "required": true, "schema": {} }],
+                    "responses": {
+                        "200": {
+                            "description": "",
+                            "content": {
+                                "application/json": {
+                                    "schema": { "$ref": "#/components/schemas/Missing" },
+                                    // Payloads may contain anything
+                                    "example": { "$ref": "#/not/a/reference" },
+                                },
+                            },
+                        },
+                    },
+                },
+                "put": { "operationId": "get", "responses": {} },
+            },
+        }));
+        let errors = validate(&openapi);
+        let errors: HashSet<_> = errors.into_iter().collect();
+        let expected: HashSet<_> = [
+            ValidationError {
+                pointer: "/paths/~1users~1{id}/get/responses/200/content/application~1json/schema"
+                    .to_string(),
+                kind: ValidationErrorKind::UnresolvedReference(
+                    "#/components/schemas/Missing".to_string(),
+                ),
+            },
+            ValidationError {
+                pointer: "/paths/~1users~1{id}/get".to_string(),
+                kind: ValidationErrorKind::UndeclaredPathParameter("id".to_string()),
+            },
+            ValidationError {
+                pointer: "/paths/~1users~1{id}/get".to_string(),
+                kind: ValidationErrorKind::UnknownPathParameter("name".to_string()),
+            },
+            ValidationError {
+                pointer: "/paths/~1users~1{id}/put".to_string(),
+                kind: ValidationErrorKind::UndeclaredPathParameter("id".to_string()),
+            },
+            ValidationError {
+                pointer: "/paths/~1users~1{id}/put".to_string(),
+                kind: ValidationErrorKind::DuplicateOperationId("get".to_string()),
+            },
+        ]
+        .into_iter()
+        .collect();
+        assert_eq!(errors, expected);
+        assert_eq!(execute(&openapi, &["validate"]), Ok(false));
+
+        assert_eq!(execute(&document(json!({})), &["validate"]), Ok(true));
+    }
+
+    #[test]
+    fn validate_resolves_parameter_references() {
+        let mut openapi = document(json!({
+            "/users/{id}": {
+                "parameters": [{ "$ref": "#/components/parameters/Id" }],
+                "get": { "responses": {} },
+            },
+        }));
+        openapi["components"]["parameters"] =
+            json!({ "Id": { "in": "path", "name": "id", "required": true, "schema": {} } });
+        assert_eq!(validate(&openapi), []);
+    }
+
+    #[test]
+    fn diff_lists_changes() {
+        let old = document(json!({
+            "/a": { "get": { "responses": {} } },
+            "/b": { "get": { "responses": {} } },
+        }));
+        let mut new = document(json!({
+            "/a": { "get": { "responses": { "200": { "description": "" } } } },
+            "/c": { "get": { "responses": {} } },
+        }));
+        new["openapi"] = json!("3.1.0");
+        new["components"]["schemas"]["Group"] = json!({ "type": "object" });
+        new["webhooks"] = json!({ "created": { "post": { "responses": {} } } });
+
+        let change = |kind, item: &str| SpecChange {
+            kind,
+            item: item.to_string(),
+        };
+        assert_eq!(
+            diff(&old, &new),
+            [
+                change(SpecChangeKind::Changed, "GET /a"),
+                change(SpecChangeKind::Removed, "GET /b"),
+                change(SpecChangeKind::Added, "GET /c"),
+                change(SpecChangeKind::Added, "POST webhook `created`"),
+                change(SpecChangeKind::Added, "schema `Group`"),
+                change(SpecChangeKind::Changed, "openapi"),
+            ]
+        );
+
+        let file = write_spec("diff.json", &old);
+        assert_eq!(execute(&new, &["diff", &file]), Ok(false));
+        assert_eq!(execute(&old, &["diff", &file]), Ok(true));
+    }
+
+    #[test]
+    fn diff_includes_path_item_parameters() {
+        let parameter = json!({ "in": "query", "name": "a", "schema": {} });
+        let old = document(json!({
+            "/": { "parameters": [parameter], "get": { "responses": {} } },
+        }));
+        let new = document(json!({
+            "/": { "get": { "parameters": [parameter], "responses": {} } },
+        }));
+        assert_eq!(diff(&old, &new), []);
+    }
+
+    #[test]
+    fn breaking_fails_only_on_breaking_changes() {
+        let old = document(json!({ "/a": { "get": { "responses": {} } } }));
+        let added = document(json!({
+            "/a": { "get": { "responses": {} } },
+            "/b": { "get": { "responses": {} } },
+        }));
+        let removed = document(json!({}));
+
+        let file = write_spec("breaking.json", &old);
+        assert_eq!(execute(&added, &["breaking", &file]), Ok(true));
+        assert_eq!(execute(&removed, &["breaking", &file]), Ok(false));
+    }
+
+    #[test]
+    fn invalid_input_is_reported() {
+        let openapi = document(json!({}));
+        assert_eq!(execute(&openapi, &["help"]), Ok(true));
+        assert!(execute(&openapi, &[]).is_err());
+        assert!(execute(&openapi, &["unknown"]).is_err());
+        assert!(execute(&openapi, &["diff", "/does/not/exist.json"]).is_err());
+
+        let file = write_spec("swagger.json", &json!({ "swagger": "2.0" }));
+        assert_eq!(
+            read_spec(&file).map_err(|error| error.contains("Missing the `openapi` version")),
+            Err(true)
+        );
+
+        assert_eq!(
+            run_with_args(&openapi, ["help".to_string()]),
+            ExitCode::SUCCESS
+        );
+        assert_eq!(
+            run_with_args(&openapi, ["validate".to_string()]),
+            ExitCode::SUCCESS
+        );
+        assert_eq!(run_with_args(&openapi, []), ExitCode::FAILURE);
+    }
+}
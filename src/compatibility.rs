@@ -0,0 +1,1169 @@
+//! Detection of breaking changes between two versions of an api
+//!
+//! [`compare`] lists the differences between two OpenAPI documents
+//! and classifies each one as breaking or non-breaking for existing clients.
+//!
+//! A typical use is a test comparing your api with a committed version of it:
+//!
+//! ```rust,no_run
+//! # use swaggapi::{PageOfEverything, SwaggapiPage};
+//! # use swaggapi::compatibility;
+//! let committed = std::fs::read_to_string("openapi.json").unwrap();
+//! let committed: serde_json::Value = serde_json::from_str(&committed).unwrap();
+//! let current = PageOfEverything.openapi_json();
+//!
+//! let breaking = compatibility::breaking_changes_json(&committed, &current);
+//! assert!(breaking.is_empty(), "{breaking:#?}");
+//! ```
+//!
+//! [`compare_json`] works with both OpenAPI 3.0 and 3.1 documents
+//! (like the ones returned by [`SwaggapiPage::openapi_json`](crate::SwaggapiPage::openapi_json)),
+//! [`compare`] is a shorthand for the 3.0 [`OpenAPI`] struct.
+//!
+//! Whether a change to a schema is breaking depends on the direction it is sent in:
+//! Restricting the values a request may contain breaks clients sending them,
+//! while extending the values a response may contain breaks clients receiving them.
+
+use std::collections::HashSet;
+use std::fmt;
+
+use openapiv3::OpenAPI;
+use serde_json::Value;
+
+use crate::internals::walk::escape;
+use crate::internals::walk::object;
+use crate::internals::walk::resolve;
+use crate::internals::walk::METHODS;
+
+/// A single difference between two versions of an api
+#[derive(Clone, Eq, PartialEq, Hash, Debug)]
+pub struct ApiChange {
+    /// The affected operation as `METHOD /path`
+    pub operation: String,
+
+    /// The affected part of the operation, for example `` query parameter `limit` ``
+    ///
+    /// Changes inside a schema are followed by the [JSON pointer](https://datatracker.ietf.org/doc/html/rfc6901)
+    /// to the changed sub schema (with references being resolved).
+    /// It is empty for changes to the operation itself.
+    pub location: String,
+
+    /// What changed
+    pub kind: ApiChangeKind,
+
+    /// Whether the change breaks existing clients
+    pub breaking: bool,
+}
+
+/// The different kinds of [`ApiChange`]
+#[derive(Clone, Eq, PartialEq, Hash, Debug)]
+#[non_exhaustive]
+pub enum ApiChangeKind {
+    /// The operation was removed
+    OperationRemoved,
+
+    /// The operation was added
+    OperationAdded,
+
+    /// The parameter was removed
+    ParameterRemoved,
+
+    /// The parameter was added
+    ParameterAdded {
+        /// Whether the new parameter is required
+        required: bool,
+    },
+
+    /// The parameter or request body became required
+    BecameRequired,
+
+    /// The parameter or request body became optional
+    BecameOptional,
+
+    /// The request body was removed
+    RequestBodyRemoved,
+
+    /// The request body was added
+    RequestBodyAdded {
+        /// Whether the new body is required
+        required: bool,
+    },
+
+    /// The response with the contained status code was removed
+    ResponseRemoved(String),
+
+    /// The response with the contained status code was added
+    ResponseAdded(String),
+
+    /// The contained content type is no longer supported
+    ContentTypeRemoved(String),
+
+    /// The contained content type is now supported
+    ContentTypeAdded(String),
+
+    /// The schema's `type` changed
+    ///
+    /// Several types (OpenAPI 3.1) are joined by ` | `.
+    TypeChanged {
+        /// The old type
+        old: String,
+        /// The new type
+        new: String,
+    },
+
+    /// The schema restricts its values to the contained `type` which it didn't do before
+    TypeAdded(String),
+
+    /// The schema no longer restricts its values to the contained `type`
+    TypeRemoved(String),
+
+    /// The contained values were removed from the schema's `enum`
+    EnumValuesRemoved(Vec<Value>),
+
+    /// The contained values were added to the schema's `enum`
+    EnumValuesAdded(Vec<Value>),
+
+    /// The contained property was removed
+    PropertyRemoved(String),
+
+    /// The contained property was added
+    PropertyAdded(String),
+
+    /// The contained property became required
+    PropertyBecameRequired(String),
+
+    /// The contained property became optional
+    PropertyBecameOptional(String),
+
+    /// The schema no longer allows `null`
+    NullableRemoved,
+
+    /// The schema now allows `null`
+    NullableAdded,
+
+    /// The contained number of alternatives was removed from the schema's `oneOf` or `anyOf`
+    VariantsRemoved(usize),
+
+    /// The contained number of alternatives was added to the schema's `oneOf` or `anyOf`
+    VariantsAdded(usize),
+
+    /// The contained number of schemas was added to the schema's `allOf`
+    AllOfSchemasAdded(usize),
+
+    /// The contained number of schemas was removed from the schema's `allOf`
+    AllOfSchemasRemoved(usize),
+}
+
+impl fmt::Display for ApiChange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let breaking = if self.breaking {
+            "breaking"
+        } else {
+            "non-breaking"
+        };
+        if self.location.is_empty() {
+            write!(f, "{} ({breaking}): {}", self.operation, self.kind)
+        } else {
+            write!(
+                f,
+                "{} {} ({breaking}): {}",
+                self.operation, self.location, self.kind
+            )
+        }
+    }
+}
+
+impl fmt::Display for ApiChangeKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::OperationRemoved => write!(f, "Operation was removed"),
+            Self::OperationAdded => write!(f, "Operation was added"),
+            Self::ParameterRemoved => write!(f, "Parameter was removed"),
+            Self::ParameterAdded { required: true } => write!(f, "Required parameter was added"),
+            Self::ParameterAdded { required: false } => write!(f, "Optional parameter was added"),
+            Self::BecameRequired => write!(f, "Became required"),
+            Self::BecameOptional => write!(f, "Became optional"),
+            Self::RequestBodyRemoved => write!(f, "Request body was removed"),
+            Self::RequestBodyAdded { required: true } => {
+                write!(f, "Required request body was added")
+            }
+            Self::RequestBodyAdded { required: false } => {
+                write!(f, "Optional request body was added")
+            }
+            Self::ResponseRemoved(status) => write!(f, "Response `{status}` was removed"),
+            Self::ResponseAdded(status) => write!(f, "Response `{status}` was added"),
+            Self::ContentTypeRemoved(mime) => write!(f, "Content type `{mime}` was removed"),
+            Self::ContentTypeAdded(mime) => write!(f, "Content type `{mime}` was added"),
+            Self::TypeChanged { old, new } => write!(f, "Type changed from `{old}` to `{new}`"),
+            Self::TypeAdded(typ) => write!(f, "Type `{typ}` was added"),
+            Self::TypeRemoved(typ) => write!(f, "Type `{typ}` was removed"),
+            Self::EnumValuesRemoved(values) => {
+                write!(f, "Enum values {} were removed", join(values))
+            }
+            Self::EnumValuesAdded(values) => write!(f, "Enum values {} were added", join(values)),
+            Self::PropertyRemoved(name) => write!(f, "Property `{name}` was removed"),
+            Self::PropertyAdded(name) => write!(f, "Property `{name}` was added"),
+            Self::PropertyBecameRequired(name) => write!(f, "Property `{name}` became required"),
+            Self::PropertyBecameOptional(name) => write!(f, "Property `{name}` became optional"),
+            Self::NullableRemoved => write!(f, "No longer nullable"),
+            Self::NullableAdded => write!(f, "Became nullable"),
+            Self::VariantsRemoved(count) => write!(f, "{count} alternative schemas were removed"),
+            Self::VariantsAdded(count) => write!(f, "{count} alternative schemas were added"),
+            Self::AllOfSchemasAdded(count) => write!(f, "{count} combined schemas were added"),
+            Self::AllOfSchemasRemoved(count) => {
+                write!(f, "{count} combined schemas were removed")
+            }
+        }
+    }
+}
+
+fn join(values: &[Value]) -> String {
+    values
+        .iter()
+        .map(|value| format!("`{value}`"))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Compares two versions of an api and lists their differences
+///
+/// (Shorthand for [`compare_json`] for OpenAPI 3.0 documents)
+pub fn compare(old: &OpenAPI, new: &OpenAPI) -> Vec<ApiChange> {
+    let old = serde_json::to_value(old).unwrap_or_default();
+    let new = serde_json::to_value(new).unwrap_or_default();
+    compare_json(&old, &new)
+}
+
+/// Compares two versions of an api and lists their breaking differences
+///
+/// (Shorthand for filtering [`compare`]'s result by [`ApiChange::breaking`])
+pub fn breaking_changes(old: &OpenAPI, new: &OpenAPI) -> Vec<ApiChange> {
+    let mut changes = compare(old, new);
+    changes.retain(|change| change.breaking);
+    changes
+}
+
+/// Compares two versions of an api given as OpenAPI 3.0 or 3.1 json and lists their differences
+///
+/// Operations are identified by their method and path,
+/// parameters by their location and name
+/// and responses by their status code.
+///
+/// The schemas of a `oneOf`, `anyOf` or `allOf` are matched regardless of their order:
+/// Equal schemas, schemas referencing the same component
+/// and schemas with the same `title` or tag (a required property with a single allowed value) are paired.
+/// Only if the remaining schemas are as many in both versions, they are paired by their position.
+pub fn compare_json(old: &Value, new: &Value) -> Vec<ApiChange> {
+    let mut comparison = Comparison {
+        old,
+        new,
+        operation: String::new(),
+        changes: Vec::new(),
+    };
+
+    let old_paths = object(&old["paths"]);
+    let new_paths = object(&new["paths"]);
+    for (path, old_item) in old_paths {
+        let new_item = new_paths.get(path).unwrap_or(&Value::Null);
+        for method in METHODS {
+            comparison.operation = format!("{} {path}", method.to_uppercase());
+            match (old_item.get(method), new_item.get(method)) {
+                (Some(old_operation), Some(new_operation)) => {
+                    comparison.operation_changes(old_item, old_operation, new_item, new_operation)
+                }
+                (Some(_), None) => comparison.push("", ApiChangeKind::OperationRemoved, true),
+                (None, Some(_)) => comparison.push("", ApiChangeKind::OperationAdded, false),
+                (None, None) => {}
+            }
+        }
+    }
+    for (path, new_item) in new_paths {
+        if old_paths.contains_key(path) {
+            continue;
+        }
+        for method in METHODS {
+            if new_item.get(method).is_some() {
+                comparison.operation = format!("{} {path}", method.to_uppercase());
+                comparison.push("", ApiChangeKind::OperationAdded, false);
+            }
+        }
+    }
+
+    comparison.changes
+}
+
+/// Compares two versions of an api given as OpenAPI 3.0 or 3.1 json and lists their breaking differences
+///
+/// (Shorthand for filtering [`compare_json`]'s result by [`ApiChange::breaking`])
+pub fn breaking_changes_json(old: &Value, new: &Value) -> Vec<ApiChange> {
+    let mut changes = compare_json(old, new);
+    changes.retain(|change| change.breaking);
+    changes
+}
+
+/// The direction a schema is sent in
+#[derive(Copy, Clone, Eq, PartialEq)]
+enum Direction {
+    /// The schema describes what clients send
+    Request,
+    /// The schema describes what clients receive
+    Response,
+}
+
+/// State of a running [`compare`]
+struct Comparison<'a> {
+    old: &'a Value,
+    new: &'a Value,
+
+    /// The operation currently being compared
+    operation: String,
+
+    changes: Vec<ApiChange>,
+}
+
+impl<'a> Comparison<'a> {
+    fn push(&mut self, location: &str, kind: ApiChangeKind, breaking: bool) {
+        self.changes.push(ApiChange {
+            operation: self.operation.clone(),
+            location: location.to_string(),
+            kind,
+            breaking,
+        });
+    }
+
+    fn operation_changes(
+        &mut self,
+        old_item: &'a Value,
+        old_operation: &'a Value,
+        new_item: &'a Value,
+        new_operation: &'a Value,
+    ) {
+        // Parameters
+        let old_parameters = parameters(self.old, old_item, old_operation);
+        let new_parameters = parameters(self.new, new_item, new_operation);
+        for (key, old_parameter) in &old_parameters {
+            let location = format!("{} parameter `{}`", key.0, key.1);
+            let Some((_, new_parameter)) = new_parameters.iter().find(|(other, _)| other == key)
+            else {
+                self.push(&location, ApiChangeKind::ParameterRemoved, false);
+                continue;
+            };
+            self.required_changes(&location, old_parameter, new_parameter);
+            self.schema_changes(
+                &location,
+                Direction::Request,
+                &old_parameter["schema"],
+                &new_parameter["schema"],
+            );
+        }
+        for (key, new_parameter) in &new_parameters {
+            if !old_parameters.iter().any(|(other, _)| other == key) {
+                let required = is_required(new_parameter);
+                self.push(
+                    &format!("{} parameter `{}`", key.0, key.1),
+                    ApiChangeKind::ParameterAdded { required },
+                    required,
+                );
+            }
+        }
+
+        // Request body
+        let old_body = resolve(self.old, &old_operation["requestBody"]);
+        let new_body = resolve(self.new, &new_operation["requestBody"]);
+        match (old_body.is_null(), new_body.is_null()) {
+            (false, false) => {
+                self.required_changes("request body", old_body, new_body);
+                self.content_changes("request body", Direction::Request, old_body, new_body);
+            }
+            (false, true) => self.push("", ApiChangeKind::RequestBodyRemoved, false),
+            (true, false) => {
+                let required = is_required(new_body);
+                self.push("", ApiChangeKind::RequestBodyAdded { required }, required);
+            }
+            (true, true) => {}
+        }
+
+        // Responses
+        let old_responses = object(&old_operation["responses"]);
+        let new_responses = object(&new_operation["responses"]);
+        for (status, old_response) in old_responses {
+            let Some(new_response) = new_responses.get(status) else {
+                // Clients might depend on a successful response
+                let breaking = status.starts_with('2');
+                self.push("", ApiChangeKind::ResponseRemoved(status.clone()), breaking);
+                continue;
+            };
+            self.content_changes(
+                &format!("response `{status}`"),
+                Direction::Response,
+                resolve(self.old, old_response),
+                resolve(self.new, new_response),
+            );
+        }
+        for status in new_responses.keys() {
+            if !old_responses.contains_key(status) {
+                self.push("", ApiChangeKind::ResponseAdded(status.clone()), false);
+            }
+        }
+    }
+
+    /// Compares the `required` flag of a parameter or request body
+    fn required_changes(&mut self, location: &str, old: &Value, new: &Value) {
+        match (is_required(old), is_required(new)) {
+            (false, true) => self.push(location, ApiChangeKind::BecameRequired, true),
+            (true, false) => self.push(location, ApiChangeKind::BecameOptional, false),
+            _ => {}
+        }
+    }
+
+    /// Compares the `content` of a request body or response
+    fn content_changes(
+        &mut self,
+        location: &str,
+        direction: Direction,
+        old: &'a Value,
+        new: &'a Value,
+    ) {
+        let old_content = object(&old["content"]);
+        let new_content = object(&new["content"]);
+        for (mime, old_media) in old_content {
+            let Some(new_media) = new_content.get(mime) else {
+                self.push(
+                    location,
+                    ApiChangeKind::ContentTypeRemoved(mime.clone()),
+                    true,
+                );
+                continue;
+            };
+            self.schema_changes(
+                &format!("{location} `{mime}`"),
+                direction,
+                &old_media["schema"],
+                &new_media["schema"],
+            );
+        }
+        for mime in new_content.keys() {
+            if !old_content.contains_key(mime) {
+                self.push(
+                    location,
+                    ApiChangeKind::ContentTypeAdded(mime.clone()),
+                    false,
+                );
+            }
+        }
+    }
+
+    fn schema_changes(
+        &mut self,
+        location: &str,
+        direction: Direction,
+        old: &'a Value,
+        new: &'a Value,
+    ) {
+        let mut visited = HashSet::new();
+        self.schema_changes_rec(location, "", direction, old, new, &mut visited);
+    }
+
+    /// Compares two schemas
+    ///
+    /// `visited` contains the pairs of references which have already been compared
+    /// to prevent infinite recursion in recursive schemas.
+    fn schema_changes_rec(
+        &mut self,
+        base: &str,
+        pointer: &str,
+        direction: Direction,
+        old: &'a Value,
+        new: &'a Value,
+        visited: &mut HashSet<(&'a str, &'a str)>,
+    ) {
+        if let (Some(old_ref), Some(new_ref)) = (reference(old), reference(new)) {
+            if !visited.insert((old_ref, new_ref)) {
+                return;
+            }
+        }
+        let old = resolve(self.old, old);
+        let new = resolve(self.new, new);
+        if old.is_null() || new.is_null() {
+            return;
+        }
+        let location = &if pointer.is_empty() {
+            base.to_string()
+        } else {
+            format!("{base} #{pointer}")
+        };
+        let is_request = direction == Direction::Request;
+
+        // Type
+        let (old_types, old_nullable) = types(old);
+        let (new_types, new_nullable) = types(new);
+        match (&old_types, &new_types) {
+            (Some(old_types), Some(new_types)) if old_types != new_types => {
+                // Requests break if a value the clients send is no longer accepted
+                // and responses break if the clients receive a value they didn't expect
+                let narrowed = old_types.iter().any(|typ| !accepts(new_types, typ));
+                let widened = new_types.iter().any(|typ| !accepts(old_types, typ));
+                self.push(
+                    location,
+                    ApiChangeKind::TypeChanged {
+                        old: join_types(old_types),
+                        new: join_types(new_types),
+                    },
+                    if is_request { narrowed } else { widened },
+                );
+                return;
+            }
+            // Schemas composing other schemas get their type from them
+            (Some(old_types), None) if !is_composition(new) => {
+                self.push(
+                    location,
+                    ApiChangeKind::TypeRemoved(join_types(old_types)),
+                    !is_request,
+                );
+            }
+            (None, Some(new_types)) if !is_composition(old) => {
+                self.push(
+                    location,
+                    ApiChangeKind::TypeAdded(join_types(new_types)),
+                    is_request,
+                );
+            }
+            _ => {}
+        }
+
+        // Nullable
+        if old_nullable && !new_nullable {
+            self.push(location, ApiChangeKind::NullableRemoved, is_request);
+        } else if !old_nullable && new_nullable {
+            self.push(location, ApiChangeKind::NullableAdded, !is_request);
+        }
+
+        // Enum
+        match (old["enum"].as_array(), new["enum"].as_array()) {
+            (Some(old_values), Some(new_values)) => {
+                let removed = difference(old_values, new_values);
+                if !removed.is_empty() {
+                    self.push(
+                        location,
+                        ApiChangeKind::EnumValuesRemoved(removed),
+                        is_request,
+                    );
+                }
+                let added = difference(new_values, old_values);
+                if !added.is_empty() {
+                    self.push(location, ApiChangeKind::EnumValuesAdded(added), !is_request);
+                }
+            }
+            // The enum was introduced which narrows the schema to its values
+            (None, Some(new_values)) => {
+                self.push(
+                    location,
+                    ApiChangeKind::EnumValuesAdded(new_values.clone()),
+                    is_request,
+                );
+            }
+            // The enum was dropped which widens the schema to any value
+            (Some(old_values), None) => {
+                self.push(
+                    location,
+                    ApiChangeKind::EnumValuesRemoved(old_values.clone()),
+                    !is_request,
+                );
+            }
+            (None, None) => {}
+        }
+
+        // Properties
+        let old_properties = object(&old["properties"]);
+        let new_properties = object(&new["properties"]);
+        let old_required = required_properties(old);
+        let new_required = required_properties(new);
+        for (name, old_property) in old_properties {
+            let Some(new_property) = new_properties.get(name) else {
+                // Clients might depend on receiving the property
+                let breaking = !is_request && old_required.contains(name.as_str());
+                self.push(
+                    location,
+                    ApiChangeKind::PropertyRemoved(name.clone()),
+                    breaking,
+                );
+                continue;
+            };
+            self.schema_changes_rec(
+                base,
+                &format!("{pointer}/properties/{}", escape(name)),
+                direction,
+                old_property,
+                new_property,
+                visited,
+            );
+        }
+        for name in new_properties.keys() {
+            if !old_properties.contains_key(name) {
+                // Clients don't send the property yet
+                let breaking = is_request && new_required.contains(name.as_str());
+                self.push(
+                    location,
+                    ApiChangeKind::PropertyAdded(name.clone()),
+                    breaking,
+                );
+            }
+        }
+        for name in new_required.difference(&old_required) {
+            let added = new_properties.contains_key(*name) && !old_properties.contains_key(*name);
+            if !added {
+                self.push(
+                    location,
+                    ApiChangeKind::PropertyBecameRequired(name.to_string()),
+                    is_request,
+                );
+            }
+        }
+        for name in old_required.difference(&new_required) {
+            if new_properties.contains_key(*name) {
+                self.push(
+                    location,
+                    ApiChangeKind::PropertyBecameOptional(name.to_string()),
+                    !is_request,
+                );
+            }
+        }
+
+        // Items and the values of maps
+        for keyword in ["items", "additionalProperties"] {
+            // `additionalProperties` may be a boolean instead of a schema
+            if let (Some(old_schema @ Value::Object(_)), Some(new_schema @ Value::Object(_))) =
+                (old.get(keyword), new.get(keyword))
+            {
+                self.schema_changes_rec(
+                    base,
+                    &format!("{pointer}/{keyword}"),
+                    direction,
+                    old_schema,
+                    new_schema,
+                    visited,
+                );
+            }
+        }
+
+        // Compositions
+        for keyword in ["oneOf", "anyOf", "allOf"] {
+            let (Some(old_schemas), Some(new_schemas)) =
+                (old[keyword].as_array(), new[keyword].as_array())
+            else {
+                continue;
+            };
+            let (pairs, removed, added) = self.pair_schemas(old_schemas, new_schemas);
+            for (old_index, new_index) in pairs {
+                self.schema_changes_rec(
+                    base,
+                    &format!("{pointer}/{keyword}/{old_index}"),
+                    direction,
+                    &old_schemas[old_index],
+                    &new_schemas[new_index],
+                    visited,
+                );
+            }
+            // Every schema of an `allOf` narrows the values
+            // while every alternative of a `oneOf` or `anyOf` extends them
+            if removed > 0 {
+                if keyword == "allOf" {
+                    self.push(
+                        location,
+                        ApiChangeKind::AllOfSchemasRemoved(removed),
+                        !is_request,
+                    );
+                } else {
+                    self.push(
+                        location,
+                        ApiChangeKind::VariantsRemoved(removed),
+                        is_request,
+                    );
+                }
+            }
+            if added > 0 {
+                if keyword == "allOf" {
+                    self.push(
+                        location,
+                        ApiChangeKind::AllOfSchemasAdded(added),
+                        is_request,
+                    );
+                } else {
+                    self.push(location, ApiChangeKind::VariantsAdded(added), !is_request);
+                }
+            }
+        }
+    }
+
+    /// Pairs the schemas of an old and a new `oneOf`, `anyOf` or `allOf` (see [`compare_json`])
+    ///
+    /// Returns the pairs of indices and the number of unpaired old and new schemas.
+    fn pair_schemas(
+        &self,
+        old_schemas: &[Value],
+        new_schemas: &[Value],
+    ) -> (Vec<(usize, usize)>, usize, usize) {
+        let mut old_left = (0..old_schemas.len()).collect::<Vec<_>>();
+        let mut new_left = (0..new_schemas.len()).collect::<Vec<_>>();
+        let mut pairs = Vec::new();
+
+        // Pair equal schemas, then schemas referencing the same component,
+        // then schemas with the same title or tag
+        let matches = |pass, old: &Value, new: &Value| match pass {
+            0 => old == new,
+            1 => reference(old).is_some() && reference(old) == reference(new),
+            _ => {
+                let key = schema_key(resolve(self.old, old));
+                key.is_some() && key == schema_key(resolve(self.new, new))
+            }
+        };
+        for pass in 0..3 {
+            old_left.retain(|&old_index| {
+                let found = new_left.iter().position(|&new_index| {
+                    matches(pass, &old_schemas[old_index], &new_schemas[new_index])
+                });
+                match found {
+                    Some(position) => {
+                        pairs.push((old_index, new_left.remove(position)));
+                        false
+                    }
+                    None => true,
+                }
+            });
+        }
+        if old_left.len() == new_left.len() {
+            pairs.extend(old_left.drain(..).zip(new_left.drain(..)));
+        }
+        pairs.sort();
+
+        (pairs, old_left.len(), new_left.len())
+    }
+}
+
+/// Collects an operation's parameters (including the path item's ones) by their location and name
+fn parameters<'a>(
+    root: &'a Value,
+    item: &'a Value,
+    operation: &'a Value,
+) -> Vec<((&'a str, &'a str), &'a Value)> {
+    let mut parameters: Vec<((&str, &str), &Value)> = Vec::new();
+    for parameter in [item, operation]
+        .into_iter()
+        .filter_map(|x| x["parameters"].as_array())
+        .flatten()
+    {
+        let parameter = resolve(root, parameter);
+        let (Some(location), Some(name)) = (parameter["in"].as_str(), parameter["name"].as_str())
+        else {
+            continue;
+        };
+        // Operation parameters override the path item's ones
+        parameters.retain(|(key, _)| *key != (location, name));
+        parameters.push(((location, name), parameter));
+    }
+    parameters
+}
+
+fn reference(value: &Value) -> Option<&str> {
+    value.get("$ref")?.as_str()
+}
+
+fn is_required(value: &Value) -> bool {
+    value["required"] == true
+}
+
+fn required_properties(schema: &Value) -> HashSet<&str> {
+    schema["required"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(Value::as_str)
+        .collect()
+}
+
+/// The values in `left` which are not in `right`
+fn difference(left: &[Value], right: &[Value]) -> Vec<Value> {
+    left.iter()
+        .filter(|value| !right.contains(value))
+        .cloned()
+        .collect()
+}
+
+/// Get the types a schema allows (except `null`) and whether it allows `null`
+///
+/// The types are `None` if the schema doesn't restrict them.
+/// Supports both OpenAPI 3.0's `nullable` and 3.1's arrays of types.
+fn types(schema: &Value) -> (Option<Vec<&str>>, bool) {
+    let mut nullable = schema["nullable"] == true;
+    let types = match &schema["type"] {
+        Value::String(typ) => Some(vec![typ.as_str()]),
+        Value::Array(types) => Some(types.iter().filter_map(Value::as_str).collect()),
+        _ => None,
+    }
+    .map(|mut types| {
+        nullable |= types.contains(&"null");
+        types.retain(|typ| *typ != "null");
+        types.sort_unstable();
+        types.dedup();
+        types
+    });
+    (types, nullable)
+}
+
+/// Whether a value of type `typ` is allowed by `types`
+fn accepts(types: &[&str], typ: &str) -> bool {
+    types.contains(&typ) || (typ == "integer" && types.contains(&"number"))
+}
+
+fn join_types(types: &[&str]) -> String {
+    if types.is_empty() {
+        "null".to_string()
+    } else {
+        types.join(" | ")
+    }
+}
+
+/// Whether a schema combines other schemas
+fn is_composition(schema: &Value) -> bool {
+    ["allOf", "oneOf", "anyOf", "$ref"]
+        .into_iter()
+        .any(|keyword| schema.get(keyword).is_some())
+}
+
+/// Get something identifying a schema among the alternatives of a `oneOf` or `anyOf`
+///
+/// This is its `title` or its tag (a required property with a single allowed value),
+/// like the one of serde's internally tagged enums.
+fn schema_key(schema: &Value) -> Option<String> {
+    if let Some(title) = schema["title"].as_str() {
+        return Some(format!("title {title}"));
+    }
+    let required = required_properties(schema);
+    let mut tags = object(&schema["properties"])
+        .iter()
+        .filter(|(name, _)| required.contains(name.as_str()))
+        .filter_map(
+            |(name, property)| match property["enum"].as_array()?.as_slice() {
+                [value] => Some(format!("tag {name}={value}")),
+                _ => None,
+            },
+        )
+        .collect::<Vec<_>>();
+    tags.sort_unstable();
+    (!tags.is_empty()).then(|| tags.join(", "))
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    /// Builds a document using `schema` as request body or `200` response of `POST /`
+    fn document(direction: Direction, schema: Value) -> Value {
+        let content = json!({ "application/json": { "schema": schema } });
+        let operation = match direction {
+            Direction::Request => json!({
+                "requestBody": { "required": true, "content": content },
+                "responses": {},
+            }),
+            Direction::Response => json!({
+                "responses": { "200": { "description": "", "content": content } },
+            }),
+        };
+        json!({ "openapi": "3.0.3", "paths": { "/": { "post": operation } } })
+    }
+
+    #[track_caller]
+    fn assert_change(changes: Vec<ApiChange>, kind: ApiChangeKind, breaking: bool) {
+        assert_eq!(changes.len(), 1, "{changes:#?}");
+        assert_eq!(changes[0].kind, kind);
+        assert_eq!(changes[0].breaking, breaking, "{}", changes[0]);
+    }
+
+    /// Generates a test comparing a schema once as request and once as response
+    /// and asserting the single change and whether it is breaking in each direction
+    macro_rules! schema_cases {
+        ($(
+            $name:ident: $old:tt => $new:tt,
+            $kind:expr,
+            request: $request:literal,
+            response: $response:literal;
+        )*) => {$(
+            mod $name {
+                use super::*;
+
+                #[test]
+                fn request() {
+                    let old = document(Direction::Request, json!($old));
+                    let new = document(Direction::Request, json!($new));
+                    assert_change(compare_json(&old, &new), $kind, $request);
+                }
+
+                #[test]
+                fn response() {
+                    let old = document(Direction::Response, json!($old));
+                    let new = document(Direction::Response, json!($new));
+                    assert_change(compare_json(&old, &new), $kind, $response);
+                }
+            }
+        )*};
+    }
+
+    schema_cases! {
+        type_changed: { "type": "string" } => { "type": "integer" },
+        ApiChangeKind::TypeChanged { old: "string".into(), new: "integer".into() },
+        request: true, response: true;
+
+        type_widened: { "type": "integer" } => { "type": "number" },
+        ApiChangeKind::TypeChanged { old: "integer".into(), new: "number".into() },
+        request: false, response: true;
+
+        type_narrowed: { "type": ["integer", "string"] } => { "type": "string" },
+        ApiChangeKind::TypeChanged { old: "integer | string".into(), new: "string".into() },
+        request: true, response: false;
+
+        type_added: {} => { "type": "string" },
+        ApiChangeKind::TypeAdded("string".into()),
+        request: true, response: false;
+
+        type_removed: { "type": "string" } => {},
+        ApiChangeKind::TypeRemoved("string".into()),
+        request: false, response: true;
+
+        enum_values_added: { "enum": ["a"] } => { "enum": ["a", "b"] },
+        ApiChangeKind::EnumValuesAdded(vec![json!("b")]),
+        request: false, response: true;
+
+        enum_values_removed: { "enum": ["a", "b"] } => { "enum": ["a"] },
+        ApiChangeKind::EnumValuesRemoved(vec![json!("b")]),
+        request: true, response: false;
+
+        enum_introduced: {} => { "enum": ["a"] },
+        ApiChangeKind::EnumValuesAdded(vec![json!("a")]),
+        request: true, response: false;
+
+        enum_dropped: { "enum": ["a"] } => {},
+        ApiChangeKind::EnumValuesRemoved(vec![json!("a")]),
+        request: false, response: true;
+
+        property_added: { "properties": {} } => { "properties": { "a": {} }, "required": ["a"] },
+        ApiChangeKind::PropertyAdded("a".into()),
+        request: true, response: false;
+
+        property_removed: { "properties": { "a": {} }, "required": ["a"] } => { "properties": {} },
+        ApiChangeKind::PropertyRemoved("a".into()),
+        request: false, response: true;
+
+        property_became_required: { "properties": { "a": {} } } => { "properties": { "a": {} }, "required": ["a"] },
+        ApiChangeKind::PropertyBecameRequired("a".into()),
+        request: true, response: false;
+
+        property_became_optional: { "properties": { "a": {} }, "required": ["a"] } => { "properties": { "a": {} } },
+        ApiChangeKind::PropertyBecameOptional("a".into()),
+        request: false, response: true;
+
+        nullable_added: { "type": "string" } => { "type": "string", "nullable": true },
+        ApiChangeKind::NullableAdded,
+        request: false, response: true;
+
+        nullable_removed: { "type": "string", "nullable": true } => { "type": "string" },
+        ApiChangeKind::NullableRemoved,
+        request: true, response: false;
+
+        nullable_added_3_1: { "type": "string" } => { "type": ["string", "null"] },
+        ApiChangeKind::NullableAdded,
+        request: false, response: true;
+
+        variants_added: { "oneOf": [{ "type": "string" }] } => { "oneOf": [{ "type": "string" }, { "type": "integer" }] },
+        ApiChangeKind::VariantsAdded(1),
+        request: false, response: true;
+
+        variants_removed: { "anyOf": [{ "type": "string" }, { "type": "integer" }] } => { "anyOf": [{ "type": "integer" }] },
+        ApiChangeKind::VariantsRemoved(1),
+        request: true, response: false;
+
+        all_of_schemas_added: { "allOf": [{ "type": "object" }] } => { "allOf": [{ "type": "object" }, { "required": ["a"] }] },
+        ApiChangeKind::AllOfSchemasAdded(1),
+        request: true, response: false;
+
+        all_of_schemas_removed: { "allOf": [{ "type": "object" }, { "required": ["a"] }] } => { "allOf": [{ "type": "object" }] },
+        ApiChangeKind::AllOfSchemasRemoved(1),
+        request: false, response: true;
+
+        items_changed: { "type": "array", "items": { "type": "string" } } => { "type": "array", "items": { "type": "boolean" } },
+        ApiChangeKind::TypeChanged { old: "string".into(), new: "boolean".into() },
+        request: true, response: true;
+
+        map_values_changed: { "type": "object", "additionalProperties": { "type": "string" } } => { "type": "object", "additionalProperties": { "type": "boolean" } },
+        ApiChangeKind::TypeChanged { old: "string".into(), new: "boolean".into() },
+        request: true, response: true;
+
+        map_values_widened: { "type": "object", "additionalProperties": { "type": "integer" } } => { "type": "object", "additionalProperties": { "type": "number" } },
+        ApiChangeKind::TypeChanged { old: "integer".into(), new: "number".into() },
+        request: false, response: true;
+    }
+
+    /// Builds a document with a single operation `POST /`
+    fn operation(operation: Value) -> Value {
+        json!({ "openapi": "3.0.3", "paths": { "/": { "post": operation } } })
+    }
+
+    /// Generates a test comparing two documents and asserting the single change and whether it is breaking
+    macro_rules! document_cases {
+        ($(
+            $name:ident: $old:expr => $new:expr,
+            $kind:expr,
+            breaking: $breaking:literal;
+        )*) => {$(
+            #[test]
+            fn $name() {
+                assert_change(compare_json(&$old, &$new), $kind, $breaking);
+            }
+        )*};
+    }
+
+    document_cases! {
+        operation_added: json!({ "paths": {} }) => operation(json!({})),
+        ApiChangeKind::OperationAdded,
+        breaking: false;
+
+        operation_removed: operation(json!({})) => json!({ "paths": {} }),
+        ApiChangeKind::OperationRemoved,
+        breaking: true;
+
+        optional_parameter_added:
+            operation(json!({})) =>
+            operation(json!({ "parameters": [{ "in": "query", "name": "a" }] })),
+        ApiChangeKind::ParameterAdded { required: false },
+        breaking: false;
+
+        required_parameter_added:
+            operation(json!({})) =>
+            operation(json!({ "parameters": [{ "in": "query", "name": "a", "required": true }] })),
+        ApiChangeKind::ParameterAdded { required: true },
+        breaking: true;
+
+        parameter_removed:
+            operation(json!({ "parameters": [{ "in": "query", "name": "a" }] })) =>
+            operation(json!({})),
+        ApiChangeKind::ParameterRemoved,
+        breaking: false;
+
+        parameter_became_required:
+            operation(json!({ "parameters": [{ "in": "query", "name": "a" }] })) =>
+            operation(json!({ "parameters": [{ "in": "query", "name": "a", "required": true }] })),
+        ApiChangeKind::BecameRequired,
+        breaking: true;
+
+        parameter_became_optional:
+            operation(json!({ "parameters": [{ "in": "query", "name": "a", "required": true }] })) =>
+            operation(json!({ "parameters": [{ "in": "query", "name": "a" }] })),
+        ApiChangeKind::BecameOptional,
+        breaking: false;
+
+        optional_request_body_added:
+            operation(json!({})) =>
+            operation(json!({ "requestBody": { "content": {} } })),
+        ApiChangeKind::RequestBodyAdded { required: false },
+        breaking: false;
+
+        required_request_body_added:
+            operation(json!({})) =>
+            operation(json!({ "requestBody": { "content": {}, "required": true } })),
+        ApiChangeKind::RequestBodyAdded { required: true },
+        breaking: true;
+
+        request_body_removed:
+            operation(json!({ "requestBody": { "content": {} } })) =>
+            operation(json!({})),
+        ApiChangeKind::RequestBodyRemoved,
+        breaking: false;
+
+        response_added:
+            operation(json!({ "responses": {} })) =>
+            operation(json!({ "responses": { "400": {} } })),
+        ApiChangeKind::ResponseAdded("400".into()),
+        breaking: false;
+
+        successful_response_removed:
+            operation(json!({ "responses": { "200": {} } })) =>
+            operation(json!({ "responses": {} })),
+        ApiChangeKind::ResponseRemoved("200".into()),
+        breaking: true;
+
+        error_response_removed:
+            operation(json!({ "responses": { "400": {} } })) =>
+            operation(json!({ "responses": {} })),
+        ApiChangeKind::ResponseRemoved("400".into()),
+        breaking: false;
+
+        content_type_added:
+            operation(json!({ "requestBody": { "content": {} } })) =>
+            operation(json!({ "requestBody": { "content": { "text/plain": {} } } })),
+        ApiChangeKind::ContentTypeAdded("text/plain".into()),
+        breaking: false;
+
+        content_type_removed:
+            operation(json!({ "requestBody": { "content": { "text/plain": {} } } })) =>
+            operation(json!({ "requestBody": { "content": {} } })),
+        ApiChangeKind::ContentTypeRemoved("text/plain".into()),
+        breaking: true;
+    }
+
+    #[test]
+    fn reordered_variants_are_paired() {
+        let cat = json!({
+            "type": "object",
+            "properties": { "type": { "enum": ["cat"] }, "lives": { "type": "integer" } },
+            "required": ["type"],
+        });
+        let dog = json!({
+            "type": "object",
+            "properties": { "type": { "enum": ["dog"] } },
+            "required": ["type"],
+        });
+        let mut changed_dog = dog.clone();
+        changed_dog["properties"]["name"] = json!({ "type": "string" });
+
+        let old = document(Direction::Response, json!({ "oneOf": [cat, dog] }));
+        let reordered = document(Direction::Response, json!({ "oneOf": [dog, cat] }));
+        assert_eq!(compare_json(&old, &reordered), []);
+
+        let changed = document(Direction::Response, json!({ "oneOf": [changed_dog, cat] }));
+        let changes = compare_json(&old, &changed);
+        assert_eq!(
+            changes,
+            [ApiChange {
+                operation: "POST /".to_string(),
+                location: "response `200` `application/json` #/oneOf/1".to_string(),
+                kind: ApiChangeKind::PropertyAdded("name".to_string()),
+                breaking: false,
+            }]
+        );
+    }
+
+    #[test]
+    fn references_are_compared_by_their_target() {
+        let mut old = document(
+            Direction::Request,
+            json!({ "oneOf": [{ "$ref": "#/components/schemas/A" }, { "type": "null" }] }),
+        );
+        let mut new = document(
+            Direction::Request,
+            json!({ "oneOf": [{ "type": "null" }, { "$ref": "#/components/schemas/A" }] }),
+        );
+        old["components"] = json!({ "schemas": { "A": { "type": "string" } } });
+        new["components"] = json!({ "schemas": { "A": { "type": "integer" } } });
+
+        let changes = compare_json(&old, &new);
+        assert_change(
+            changes,
+            ApiChangeKind::TypeChanged {
+                old: "string".into(),
+                new: "integer".into(),
+            },
+            true,
+        );
+    }
+}
@@ -1,7 +1,14 @@
+use std::sync::Arc;
+
+use openapiv3::OpenAPI;
+
 use crate::internals::SwaggapiHandler;
 use crate::internals::SwaggapiPageBuilderImpl;
-use crate::internals::{AccessSwaggapiPageBuilder, ContextHandler};
-use crate::page::SwaggapiPageBuilder;
+use crate::internals::{AccessSwaggapiPageBuilder, ContextHandler, PageHandle};
+use crate::page::RuntimePage;
+use crate::registry::Registry;
+use crate::security::SecurityRequirement;
+use crate::tags::Tag;
 use crate::PageOfEverything;
 use crate::SwaggapiPage;
 
@@ -18,6 +25,9 @@ pub struct ApiContext<Impl> {
     /// This is a `Router` for axum and a `Scope` for actix.
     framework_impl: Impl,
 
+    /// The registry the handlers are added to once mounted
+    registry: Registry,
+
     /* Parameters added to new handlers */
     /// A base path all handlers are routed under
     ///
@@ -26,10 +36,16 @@ pub struct ApiContext<Impl> {
     path: String,
 
     /// Changes have to be applied to already existing `handlers` manually
-    pages: Vec<&'static SwaggapiPageBuilder>,
+    pages: Vec<PageHandle>,
 
     /// Changes have to be applied to already existing `handlers` manually
     tags: Vec<&'static str>,
+
+    /// Changes have to be applied to already existing `handlers` manually
+    tag_definitions: Vec<Tag>,
+
+    /// Changes have to be applied to already existing `handlers` manually
+    security: Vec<SecurityRequirement>,
 }
 
 impl<T> ApiContext<T> {
@@ -37,14 +53,26 @@ impl<T> ApiContext<T> {
         Self {
             handlers: Vec::new(),
             framework_impl,
+            registry: Registry::global(),
 
             path,
             pages: Vec::new(),
             tags: Vec::new(),
+            tag_definitions: Vec::new(),
+            security: Vec::new(),
         }
     }
 
     /// Add a handler to the context
+    ///
+    /// Several handlers may share the same path as long as they use different http methods.
+    /// Both the framework's router and the [`SwaggapiPage`]s will combine them into a single route.
+    ///
+    /// # Panics
+    /// With axum, if the handler requires a state other than the context's `S` (or `()`).
+    /// The state is taken from the handler's `State<...>` argument,
+    /// handlers using a sub state through [`FromRef`](axum::extract::FromRef)
+    /// have to set the context's state explicitly using `#[handler(..., state = ...)]`.
     #[allow(private_bounds)]
     pub fn handler(mut self, handler: SwaggapiHandler) -> Self
     where
@@ -55,12 +83,21 @@ impl<T> ApiContext<T> {
         ValidFrameworkImpl::handler(self, handler)
     }
 
+    /// Set the [`Registry`] this context's handlers will be added to
+    ///
+    /// Only the registry of the outermost context (the one mounted to your application) is used.
+    pub fn registry(mut self, registry: Registry) -> Self {
+        self.registry = registry;
+        self
+    }
+
     /// Attach a [`SwaggapiPage`] this context's handlers will be added to
     pub fn page(mut self, page: impl SwaggapiPage) -> Self {
-        self.pages.push(page.get_builder());
+        let page = page.get_builder();
         for handler in &mut self.handlers {
-            handler.pages.insert(page.get_builder());
+            add_page(handler, &page);
         }
+        self.pages.push(page);
         self
     }
 
@@ -73,22 +110,100 @@ impl<T> ApiContext<T> {
         self
     }
 
-    /// Adds a [`ContextHandler`] after adding this context's `path`, `tags` and `pages` to it
+    /// Add a tag to all of this context's handlers and add its definition to their pages
+    ///
+    /// See [`SwaggapiPageBuilder::tags`](crate::SwaggapiPageBuilder::tags) to define tags on the page itself.
+    pub fn tag_definition(mut self, tag: Tag) -> Self {
+        self.tags.push(tag.name);
+        self.tag_definitions.push(tag);
+        for handler in &mut self.handlers {
+            handler.tags.insert(tag.name);
+            handler.tag_definitions.push(tag);
+        }
+        self
+    }
+
+    /// Add a security requirement to all of this context's handlers
+    ///
+    /// Multiple requirements are alternatives, i.e. a request has to satisfy only one of them.
+    /// The schemes it references have to be declared on the [`SwaggapiPage`]s
+    /// using [`SwaggapiPageBuilder::security_schemes`](crate::SwaggapiPageBuilder::security_schemes).
+    pub fn security(mut self, requirement: SecurityRequirement) -> Self {
+        self.security.push(requirement);
+        for handler in &mut self.handlers {
+            add_security(handler, requirement);
+        }
+        self
+    }
+
+    /// Assembles a page from this context's handlers without mounting the context
+    ///
+    /// The returned page has the same configuration as `page`
+    /// and contains the handlers which have been attached to `page`
+    /// (or every handler if `page` is the [`PageOfEverything`]).
+    /// Webhooks and schemas added directly to `page` are not included.
+    ///
+    /// Neither `page` nor the context's [`Registry`] are modified,
+    /// so this can be used to write the OpenAPI document from a test or a build script:
+    ///
+    /// ```rust
+    /// # use swaggapi::{ApiContext, SwaggapiPage, SwaggapiPageBuilder};
+    /// static PAGE: SwaggapiPageBuilder = SwaggapiPageBuilder::new().title("My api");
+    ///
+    /// fn write_openapi<T>(context: &ApiContext<T>) -> std::io::Result<()> {
+    ///     let json = context.build_page(&PAGE).openapi_json();
+    ///     std::fs::write("openapi.json", json.to_string())
+    /// }
+    /// ```
+    pub fn build_page(&self, page: impl SwaggapiPage) -> RuntimePage {
+        let page = page.get_builder();
+        let everything = page == PageOfEverything.get_builder();
+
+        let copy = RuntimePage::new(page.copy_config());
+        let builder = copy.get_builder();
+        for handler in &self.handlers {
+            if everything || handler.pages.contains(&page) {
+                SwaggapiPageBuilderImpl::add_handler(&builder, handler);
+            }
+        }
+        copy
+    }
+
+    /// Assembles the OpenAPI document of all this context's handlers without mounting the context
+    ///
+    /// (Shorthand for `context.build_page(PageOfEverything).openapi()`, see [`ApiContext::build_page`])
+    pub fn openapi(&self) -> Arc<OpenAPI> {
+        self.build_page(PageOfEverything).openapi()
+    }
+
+    /// Adds a [`ContextHandler`] after adding this context's `path`, `tags`, `tag_definitions`, `security` and `pages` to it
     fn push_handler(&mut self, mut handler: ContextHandler) {
         if !self.path.is_empty() {
             handler.path = format!("{}{}", self.path, handler.path);
         }
         handler.tags.extend(self.tags.iter().copied());
-        handler.pages.extend(self.pages.iter().copied());
+        handler
+            .tag_definitions
+            .extend(self.tag_definitions.iter().copied());
+        for requirement in &self.security {
+            add_security(&mut handler, *requirement);
+        }
+        for page in &self.pages {
+            add_page(&mut handler, page);
+        }
         self.handlers.push(handler);
     }
 
     /// Adds the handlers to their api pages and returns the contained framework impl
     fn finish(self) -> T {
+        let everything = self.registry.everything();
         for mut handler in self.handlers {
-            SwaggapiPageBuilderImpl::add_handler(PageOfEverything.get_builder(), &handler);
-            for page in handler.pages.iter() {
-                SwaggapiPageBuilderImpl::add_handler(page, &handler);
+            SwaggapiPageBuilderImpl::add_handler(&everything, &handler);
+            for page in &handler.pages {
+                let page = self.registry.page(page.clone());
+                if page != everything {
+                    SwaggapiPageBuilderImpl::add_handler(&page, &handler);
+                }
             }
         }
         self.framework_impl
@@ -100,18 +215,38 @@ impl<T> ApiContext<T> {
             handlers,
             pages,
             tags,
+            tag_definitions,
+            security,
             framework_impl,
+            registry,
         } = self;
         ApiContext {
             path,
             handlers,
+            registry,
             pages,
             tags,
+            tag_definitions,
+            security,
             framework_impl: func(framework_impl),
         }
     }
 }
 
+/// Adds a page to a handler unless it is already present
+fn add_page(handler: &mut ContextHandler, page: &PageHandle) {
+    if !handler.pages.contains(page) {
+        handler.pages.push(page.clone());
+    }
+}
+
+/// Adds a security requirement to a handler unless it is already present
+fn add_security(handler: &mut ContextHandler, requirement: SecurityRequirement) {
+    if !handler.security.contains(&requirement) {
+        handler.security.push(requirement);
+    }
+}
+
 /// Helper trait to have framework independent methods
 /// use framework specific implementations
 trait ValidFrameworkImpl {
@@ -242,20 +377,32 @@ const _: () = {
 
     use axum::extract::Request;
     use axum::response::IntoResponse;
+    use axum::routing::MethodRouter;
     use axum::routing::Route;
     use axum::routing::Router;
     use tower::Layer;
     use tower::Service;
 
-    impl ApiContext<Router> {
+    impl<S> ApiContext<Router<S>>
+    where
+        S: Clone + Send + Sync + 'static,
+    {
         /// Create a new context
         ///
         /// It wraps an axum [`Router`] internally and should be added to your application's router using [`Router::merge`]:
         /// ```rust
         /// # use axum::Router;
         /// # use swaggapi::ApiContext;
-        /// let app = Router::new().merge(ApiContext::new("/api"));
+        /// let app: Router = Router::new().merge(ApiContext::new());
         /// ```
+        ///
+        /// Like the `Router` it wraps, the context is generic over the state its handlers require.
+        /// Handlers without state can be added to any context.
+        ///
+        /// A handler's state is only checked when it is added:
+        /// [`ApiContext::handler`] panics if it requires a state other than `S`.
+        /// A handler extracting a sub state of `S` through [`FromRef`](axum::extract::FromRef)
+        /// has to declare `S` with `#[handler(..., state = S)]`.
         pub fn new() -> Self {
             Self::with_framework_impl(String::new(), Router::new())
         }
@@ -268,7 +415,7 @@ const _: () = {
         }
 
         /// Calls [`Router::nest`] while preserving api information
-        pub fn nest(mut self, path: &str, other: ApiContext<Router>) -> Self {
+        pub fn nest(mut self, path: &str, other: ApiContext<Router<S>>) -> Self {
             for mut handler in other.handlers {
                 // Code taken from `path_for_nested_route` in `axum/src/routing/path_router.rs`
                 handler.path = if path.ends_with('/') {
@@ -285,7 +432,7 @@ const _: () = {
         }
 
         /// Calls [`Router::merge`] while preserving api information
-        pub fn merge(mut self, other: ApiContext<Router>) -> Self {
+        pub fn merge(mut self, other: ApiContext<Router<S>>) -> Self {
             for handler in other.handlers {
                 self.push_handler(handler);
             }
@@ -321,14 +468,40 @@ const _: () = {
         }
     }
 
-    impl ValidFrameworkImpl for ApiContext<Router> {
+    impl<S> Default for ApiContext<Router<S>>
+    where
+        S: Clone + Send + Sync + 'static,
+    {
+        fn default() -> Self {
+            Self::new()
+        }
+    }
+
+    impl<S> ValidFrameworkImpl for ApiContext<Router<S>>
+    where
+        S: Clone + Send + Sync + 'static,
+    {
         fn handler(self, handler: SwaggapiHandler) -> Self {
-            self.map_framework_impl(|x| x.route(&handler.path, (handler.axum)()))
+            let method_router = match (handler.axum)().downcast::<MethodRouter<S>>() {
+                Ok(method_router) => *method_router,
+                Err(method_router) => match method_router.downcast::<MethodRouter>() {
+                    Ok(method_router) => method_router.with_state(()),
+                    Err(_) => panic!(
+                        "The handler `{}` requires a different state than `{}`",
+                        handler.ident,
+                        std::any::type_name::<S>(),
+                    ),
+                },
+            };
+            self.map_framework_impl(|x| x.route(&handler.path, method_router))
         }
     }
 
-    impl From<ApiContext<Router>> for Router {
-        fn from(context: ApiContext<Router>) -> Self {
+    impl<S> From<ApiContext<Router<S>>> for Router<S>
+    where
+        S: Clone + Send + Sync + 'static,
+    {
+        fn from(context: ApiContext<Router<S>>) -> Self {
             context.finish()
         }
     }
@@ -0,0 +1,188 @@
+//! Problems found while building a [`SwaggapiPage`](crate::SwaggapiPage)
+//!
+//! Use [`SwaggapiPage::diagnostics`](crate::SwaggapiPage::diagnostics) to inspect them
+//! and [`SwaggapiPage::validate`](crate::SwaggapiPage::validate)
+//! or [`SwaggapiPageBuilder::strict`](crate::SwaggapiPageBuilder::strict) to turn them into errors.
+
+use std::fmt;
+
+use crate::internals::HttpMethod;
+
+/// Something which got lost while converting a [`schemars`] schema into an OpenAPI 3.0 one
+#[derive(Clone, Eq, PartialEq, Hash, Debug)]
+pub struct ConversionDiagnostic {
+    /// The name of the converted schema
+    ///
+    /// This is the schema's key in `components/schemas`
+    /// or the rust type's name for schemas which are inlined.
+    pub schema: String,
+
+    /// [JSON pointer](https://datatracker.ietf.org/doc/html/rfc6901) to the offending sub schema
+    ///
+    /// The pointer is relative to the schema named by [`ConversionDiagnostic::schema`]
+    /// and empty if the problem is in its root.
+    pub pointer: String,
+
+    /// What went wrong
+    pub kind: ConversionDiagnosticKind,
+}
+
+/// The different kinds of [`ConversionDiagnostic`]
+#[derive(Clone, Eq, PartialEq, Hash, Debug)]
+#[non_exhaustive]
+pub enum ConversionDiagnosticKind {
+    /// A JSON Schema keyword which OpenAPI 3.0 doesn't support was dropped
+    UnsupportedKeyword(&'static str),
+
+    /// `enum` values of the contained type were dropped because OpenAPI 3.0 can't express them
+    UnsupportedEnum(&'static str),
+
+    /// The schema had several examples, only the first one was kept
+    MultipleExamples,
+
+    /// An instance type was listed more than once
+    DuplicateInstanceType(&'static str),
+
+    /// Both the inclusive and the exclusive variant of the contained bound were set,
+    /// only the exclusive one was kept
+    ConflictingBounds(&'static str),
+
+    /// The contained constraint of an integer schema had decimals which were truncated
+    FractionalIntegerConstraint(&'static str),
+
+    /// A length or count constraint didn't fit into `usize` and was saturated
+    LengthOverflow,
+}
+
+/// Two handlers added to the same page which share the same http method and path
+///
+/// Paths which only differ in the names of their parameters (`/users/{id}` and `/users/{user_id}`) are the same.
+/// Only one of them can be documented,
+/// which one is decided by [`SwaggapiPageBuilder::on_duplicate_route`](crate::SwaggapiPageBuilder::on_duplicate_route).
+#[derive(Clone, Eq, PartialEq, Hash, Debug)]
+pub struct DuplicateRoute {
+    /// The http method both handlers handle
+    pub method: HttpMethod,
+
+    /// The path the first handler is mounted on
+    pub path: String,
+
+    /// The path the second handler is mounted on
+    ///
+    /// This is [`DuplicateRoute::path`] with possibly different names for the path parameters.
+    pub second_path: String,
+
+    /// The handler which was added first, as `module::path::ident`
+    pub first: String,
+
+    /// The handler which was added second, as `module::path::ident`
+    pub second: String,
+}
+
+/// A handler with several arguments consuming the request body
+///
+/// Only one of them can read the body, so the handler will fail to extract its arguments.
+/// The handler is documented with the request body of the last of them
+/// which is an error for [`strict`](crate::SwaggapiPageBuilder::strict) pages.
+///
+/// Alternative bodies (like actix' `Either<Json<A>, Form<B>>`) are a single argument and not a conflict.
+#[derive(Clone, Eq, PartialEq, Hash, Debug)]
+pub struct ConflictingRequestBodies {
+    /// The handler, as `module::path::ident`
+    pub handler: String,
+
+    /// The (1-based) positions of the arguments consuming the body
+    pub arguments: Vec<usize>,
+}
+
+/// An `operationId` of a handler which was already used by another operation
+///
+/// `operationId`s have to be unique across the entire api,
+/// so the handler's one is suffixed with a number.
+/// Operations are processed sorted by their path (webhooks after paths),
+/// which makes the renaming independent of the order the handlers were added in.
+///
+/// Explicit `operation_id`s are only renamed if another handler set the same one
+/// which is an error for [`strict`](crate::SwaggapiPageBuilder::strict) pages.
+#[derive(Clone, Eq, PartialEq, Hash, Debug)]
+pub struct DuplicateOperationId {
+    /// The renamed operation, as `METHOD /path` or `METHOD webhook`
+    pub operation: String,
+
+    /// The `operationId` which was already used
+    pub operation_id: String,
+
+    /// The unique `operationId` the operation got instead
+    pub renamed: String,
+
+    /// Was the `operationId` set explicitly using the handler's `operation_id`?
+    pub explicit: bool,
+}
+
+impl fmt::Display for ConversionDiagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}#{}: {}", self.schema, self.pointer, self.kind)
+    }
+}
+
+impl fmt::Display for ConversionDiagnosticKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnsupportedKeyword(keyword) => write!(f, "Can't convert `{keyword}`"),
+            Self::UnsupportedEnum(typ) => write!(f, "Can't convert `enum` for type `{typ}`"),
+            Self::MultipleExamples => write!(f, "Only the first of the schema's examples is used"),
+            Self::DuplicateInstanceType(typ) => {
+                write!(f, "Instance type `{typ}` is specified multiple times")
+            }
+            Self::ConflictingBounds(bound) => {
+                write!(f, "`{bound}` and its exclusive variant are both set")
+            }
+            Self::FractionalIntegerConstraint(keyword) => {
+                write!(f, "Integer type has a decimal `{keyword}`")
+            }
+            Self::LengthOverflow => write!(f, "Couldn't convert u32 lossless to usize"),
+        }
+    }
+}
+
+impl fmt::Display for DuplicateRoute {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} {} is handled by both `{}` and `{}`",
+            self.method, self.path, self.first, self.second
+        )?;
+        if self.second_path != self.path {
+            write!(f, " (as {})", self.second_path)?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for ConflictingRequestBodies {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "The handler `{}` has several arguments consuming the request body (arguments {})",
+            self.handler,
+            self.arguments
+                .iter()
+                .map(usize::to_string)
+                .collect::<Vec<_>>()
+                .join(", ")
+        )
+    }
+}
+
+impl fmt::Display for DuplicateOperationId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} uses the already used {}operationId `{}` and has been renamed to `{}`",
+            self.operation,
+            if self.explicit { "explicit " } else { "" },
+            self.operation_id,
+            self.renamed
+        )
+    }
+}
@@ -4,11 +4,14 @@ use actix_web::web;
 use log::{debug, warn};
 use openapiv3::ParameterSchemaOrContent;
 use openapiv3::RequestBody;
+use openapiv3::Responses;
 use openapiv3::{Parameter, ReferenceOr, Schema, SchemaKind};
 use openapiv3::{ParameterData, Type};
 use schemars::JsonSchema;
 use serde::de::DeserializeOwned;
 
+use crate::as_responses::merge_responses;
+use crate::handler_argument::rejection_responses;
 use crate::handler_argument::simple_request_body;
 use crate::handler_argument::HandlerArgument;
 use crate::handler_argument::ShouldBeHandlerArgument;
@@ -44,6 +47,13 @@ impl<T: DeserializeOwned + JsonSchema> HandlerArgument for web::Json<T> {
             schema: Some(schema),
         }))
     }
+
+    fn rejection_responses(_gen: &mut SchemaGenerator) -> Responses {
+        rejection_responses([(
+            400,
+            "Request body is not valid json or doesn't match the expected schema",
+        )])
+    }
 }
 
 impl<T> ShouldBeHandlerArgument for web::Form<T> {}
@@ -55,6 +65,16 @@ impl<T: DeserializeOwned + JsonSchema> HandlerArgument for web::Form<T> {
             schema: Some(schema),
         }))
     }
+
+    fn rejection_responses(_gen: &mut SchemaGenerator) -> Responses {
+        rejection_responses([
+            (400, "Failed to deserialize the form"),
+            (
+                415,
+                "Missing `Content-Type: application/x-www-form-urlencoded` header",
+            ),
+        ])
+    }
 }
 
 impl<T> ShouldBeHandlerArgument for web::Path<T> {}
@@ -111,6 +131,10 @@ impl<T: DeserializeOwned + JsonSchema> HandlerArgument for web::Path<T> {
             }
         }
     }
+
+    fn rejection_responses(_gen: &mut SchemaGenerator) -> Responses {
+        rejection_responses([(404, "Path parameters don't match the expected schema")])
+    }
 }
 
 impl<T> ShouldBeHandlerArgument for web::Query<T> {}
@@ -141,6 +165,57 @@ impl<T: DeserializeOwned + JsonSchema> HandlerArgument for web::Query<T> {
             })
             .collect()
     }
+
+    fn rejection_responses(_gen: &mut SchemaGenerator) -> Responses {
+        rejection_responses([(400, "Query parameters don't match the expected schema")])
+    }
+}
+
+impl<H> ShouldBeHandlerArgument for web::Header<H> {}
+impl<H: actix_web::http::header::Header> HandlerArgument for web::Header<H> {
+    fn parameters(gen: &mut SchemaGenerator, _path: &[&str]) -> Vec<Parameter> {
+        vec![Parameter::Header {
+            parameter_data: ParameterData {
+                name: H::name().to_string(),
+                description: None,
+                required: true,
+                deprecated: None,
+                format: ParameterSchemaOrContent::Schema(gen.generate::<String>()),
+                example: None,
+                examples: Default::default(),
+                explode: None,
+                extensions: Default::default(),
+            },
+            style: Default::default(),
+        }]
+    }
+
+    fn rejection_responses(_gen: &mut SchemaGenerator) -> Responses {
+        rejection_responses([(400, "Missing or invalid header")])
+    }
+}
+
+impl<L, R> ShouldBeHandlerArgument for actix_web::Either<L, R> {}
+impl<L: HandlerArgument, R: HandlerArgument> HandlerArgument for actix_web::Either<L, R> {
+    fn request_body(gen: &mut SchemaGenerator) -> Option<RequestBody> {
+        match (L::request_body(gen), R::request_body(gen)) {
+            (Some(mut left), Some(right)) => {
+                for (mime_type, media_type) in right.content {
+                    left.content.entry(mime_type).or_insert(media_type);
+                }
+                left.description = left.description.or(right.description);
+                left.required &= right.required;
+                Some(left)
+            }
+            (left, right) => left.or(right),
+        }
+    }
+
+    fn rejection_responses(gen: &mut SchemaGenerator) -> Responses {
+        let mut responses = L::rejection_responses(gen);
+        merge_responses(&mut responses, R::rejection_responses(gen));
+        responses
+    }
 }
 
 impl ShouldBeHandlerArgument for web::Payload {}
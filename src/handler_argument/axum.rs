@@ -4,16 +4,19 @@ use axum::body::Bytes;
 use axum::extract::Path;
 use axum::extract::Query;
 use axum::extract::RawForm;
+use axum::extract::State;
 use axum::Form;
 use axum::Json;
 use log::{debug, warn};
 use openapiv3::ParameterSchemaOrContent;
 use openapiv3::RequestBody;
+use openapiv3::Responses;
 use openapiv3::{Parameter, SchemaKind, Type};
 use openapiv3::{ParameterData, ReferenceOr};
 use schemars::JsonSchema;
 use serde::de::DeserializeOwned;
 
+use crate::handler_argument::rejection_responses;
 use crate::handler_argument::simple_request_body;
 use crate::handler_argument::HandlerArgument;
 use crate::handler_argument::ShouldBeHandlerArgument;
@@ -49,23 +52,47 @@ impl<T: DeserializeOwned + JsonSchema> HandlerArgument for Json<T> {
             schema: Some(schema),
         }))
     }
+
+    fn rejection_responses(_gen: &mut SchemaGenerator) -> Responses {
+        rejection_responses([
+            (400, "Request body is not valid json"),
+            (415, "Missing `Content-Type: application/json` header"),
+            (422, "Request body doesn't match the expected schema"),
+        ])
+    }
 }
 
 impl<T> ShouldBeHandlerArgument for Form<T> {}
-/*
 impl<T: DeserializeOwned + JsonSchema> HandlerArgument for Form<T> {
     fn request_body(gen: &mut SchemaGenerator) -> Option<RequestBody> {
-        let schema = convert_schema(gen.generate::<T>());
+        let schema = gen.generate::<T>();
         Some(simple_request_body(SimpleRequestBody {
             mime_type: mime::APPLICATION_WWW_FORM_URLENCODED,
             schema: Some(schema),
         }))
     }
+
+    fn parameters(gen: &mut SchemaGenerator, path: &[&str]) -> Vec<Parameter> {
+        <Query<T> as HandlerArgument>::parameters(gen, path)
+    }
+
+    fn query_for_get_and_head() -> bool {
+        true
+    }
+
+    fn rejection_responses(_gen: &mut SchemaGenerator) -> Responses {
+        rejection_responses([
+            (400, "Failed to deserialize the form"),
+            (
+                415,
+                "Missing `Content-Type: application/x-www-form-urlencoded` header",
+            ),
+            (422, "Request body doesn't match the expected schema"),
+        ])
+    }
 }
-*/
 
 impl ShouldBeHandlerArgument for RawForm {}
-/*
 impl HandlerArgument for RawForm {
     fn request_body(_gen: &mut SchemaGenerator) -> Option<RequestBody> {
         Some(simple_request_body(SimpleRequestBody {
@@ -73,10 +100,20 @@ impl HandlerArgument for RawForm {
             schema: None,
         }))
     }
+
+    fn query_for_get_and_head() -> bool {
+        true
+    }
+
+    fn rejection_responses(_gen: &mut SchemaGenerator) -> Responses {
+        rejection_responses([(
+            415,
+            "Missing `Content-Type: application/x-www-form-urlencoded` header",
+        )])
+    }
 }
-*/
 
-#[cfg(feature = "dep:axum/multipart")]
+#[cfg(feature = "axum-multipart")]
 const _: () = {
     use axum::extract::Multipart;
     impl ShouldBeHandlerArgument for Multipart {}
@@ -87,9 +124,16 @@ const _: () = {
                 schema: None,
             }))
         }
+
+        fn rejection_responses(_gen: &mut SchemaGenerator) -> Responses {
+            rejection_responses([(400, "Invalid multipart request body")])
+        }
     }
 };
 
+impl<S> ShouldBeHandlerArgument for State<S> {}
+impl<S> HandlerArgument for State<S> {}
+
 impl<T> ShouldBeHandlerArgument for Path<T> {}
 impl<T: DeserializeOwned + JsonSchema> HandlerArgument for Path<T> {
     fn parameters(gen: &mut SchemaGenerator, path: &[&str]) -> Vec<Parameter> {
@@ -144,6 +188,10 @@ impl<T: DeserializeOwned + JsonSchema> HandlerArgument for Path<T> {
             }
         }
     }
+
+    fn rejection_responses(_gen: &mut SchemaGenerator) -> Responses {
+        rejection_responses([(400, "Path parameters don't match the expected schema")])
+    }
 }
 
 impl<T> ShouldBeHandlerArgument for Query<T> {}
@@ -174,4 +222,42 @@ impl<T: DeserializeOwned + JsonSchema> HandlerArgument for Query<T> {
             })
             .collect()
     }
+
+    fn rejection_responses(_gen: &mut SchemaGenerator) -> Responses {
+        rejection_responses([(400, "Query parameters don't match the expected schema")])
+    }
 }
+
+#[cfg(feature = "axum-extra")]
+impl<H> ShouldBeHandlerArgument for axum_extra::TypedHeader<H> {}
+#[cfg(feature = "axum-extra")]
+impl<H: axum_extra::headers::Header> HandlerArgument for axum_extra::TypedHeader<H> {
+    fn parameters(gen: &mut SchemaGenerator, _path: &[&str]) -> Vec<Parameter> {
+        vec![Parameter::Header {
+            parameter_data: ParameterData {
+                name: H::name().to_string(),
+                description: None,
+                required: true,
+                deprecated: None,
+                format: ParameterSchemaOrContent::Schema(gen.generate::<String>()),
+                example: None,
+                examples: Default::default(),
+                explode: None,
+                extensions: Default::default(),
+            },
+            style: Default::default(),
+        }]
+    }
+
+    fn rejection_responses(_gen: &mut SchemaGenerator) -> Responses {
+        rejection_responses([(400, "Missing or invalid header")])
+    }
+}
+
+/// The cookies read from a [`CookieJar`](axum_extra::extract::CookieJar) are not known statically.
+///
+/// Use [`TypedHeaders`](crate::headers::TypedHeaders) with `#[cookie]` fields to document them.
+#[cfg(feature = "axum-extra")]
+impl ShouldBeHandlerArgument for axum_extra::extract::CookieJar {}
+#[cfg(feature = "axum-extra")]
+impl HandlerArgument for axum_extra::extract::CookieJar {}
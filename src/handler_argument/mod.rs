@@ -4,6 +4,8 @@
 mod actix;
 #[cfg(feature = "axum")]
 mod axum;
+#[cfg(feature = "multipart")]
+mod multipart;
 mod utils;
 
 use indexmap::IndexMap;
@@ -12,9 +14,14 @@ use openapiv3::MediaType;
 use openapiv3::Parameter;
 use openapiv3::ReferenceOr;
 use openapiv3::RequestBody;
+use openapiv3::Responses;
 use openapiv3::Schema;
+use openapiv3::StatusCode;
 
+use crate::as_responses::simple_responses;
+use crate::as_responses::SimpleResponse;
 use crate::internals::SchemaGenerator;
+use crate::security::SecurityScheme;
 
 /// Marker trait
 pub trait ShouldBeHandlerArgument {}
@@ -30,6 +37,9 @@ pub trait HandlerArgument: ShouldBeHandlerArgument {
     /// Get the [request body object](https://spec.openapis.org/oas/v3.0.3#request-body-object) describing `Self`
     ///
     /// Should return `None` if `Self` doesn't consume the request body
+    ///
+    /// Handlers with more than one argument consuming the body are reported by [`SwaggapiPage::conflicting_request_bodies`](crate::SwaggapiPage::conflicting_request_bodies).
+    /// Alternative bodies (like actix' `Either<Json<A>, Form<B>>`) should be combined into a single request body.
     fn request_body(_gen: &mut SchemaGenerator) -> Option<RequestBody> {
         None
     }
@@ -40,6 +50,47 @@ pub trait HandlerArgument: ShouldBeHandlerArgument {
     fn parameters(_gen: &mut SchemaGenerator, _path: &[&str]) -> Vec<Parameter> {
         Vec::new()
     }
+
+    /// Should return `true` if `Self` reads the query string instead of the body for `GET` and `HEAD` requests
+    ///
+    /// If it does, the page builder will use [`HandlerArgument::parameters`] for these methods
+    /// and [`HandlerArgument::request_body`] for every other one.
+    fn query_for_get_and_head() -> bool {
+        false
+    }
+
+    /// Get the [security schemes](https://spec.openapis.org/oas/v3.0.3#security-scheme-object) `Self` authenticates a request with
+    ///
+    /// The schemes are added to every page the handler is added to
+    /// and the handler will require all of them (in addition to each requirement from its `security(...)` attribute).
+    /// Return several schemes only if a request has to satisfy all of them,
+    /// alternatives should be declared on the handler instead.
+    ///
+    /// Should return an empty `Vec` if `Self` doesn't perform any authentication
+    fn security_schemes() -> Vec<(&'static str, SecurityScheme)> {
+        Vec::new()
+    }
+
+    /// Get additional [responses](https://spec.openapis.org/oas/v3.0.3#responses-object) a handler might produce because of `Self`
+    ///
+    /// For example, an extractor authenticating the request could add a `401` response
+    /// using [`unauthorized`](crate::as_responses::unauthorized).
+    ///
+    /// Responses for status codes which are already described by the handler's return type are ignored.
+    fn responses(_gen: &mut SchemaGenerator) -> Responses {
+        Responses::default()
+    }
+
+    /// Get the [responses](https://spec.openapis.org/oas/v3.0.3#responses-object) the framework sends when `Self` rejects a request
+    ///
+    /// For example, a json extractor might reject a request with `400`, `415` or `422`.
+    /// Use [`rejection_responses`] to build them.
+    ///
+    /// Unlike [`HandlerArgument::responses`] these are only added
+    /// if the page enabled them (see [`SwaggapiPageBuilder::rejection_responses`](crate::SwaggapiPageBuilder::rejection_responses)).
+    fn rejection_responses(_gen: &mut SchemaGenerator) -> Responses {
+        Responses::default()
+    }
 }
 
 /// Struct representation of a [`HandlerArgument`]
@@ -47,6 +98,11 @@ pub trait HandlerArgument: ShouldBeHandlerArgument {
 pub struct HandlerArgumentFns {
     pub(crate) request_body: fn(&mut SchemaGenerator) -> Option<RequestBody>,
     pub(crate) parameters: fn(&mut SchemaGenerator, &[&str]) -> Vec<Parameter>,
+    pub(crate) query_for_get_and_head: fn() -> bool,
+    /// Schemes which are all required, see [`HandlerArgument::security_schemes`]
+    pub(crate) security_schemes: fn() -> Vec<(&'static str, SecurityScheme)>,
+    pub(crate) responses: fn(&mut SchemaGenerator) -> Responses,
+    pub(crate) rejection_responses: fn(&mut SchemaGenerator) -> Responses,
 }
 
 /// Helper function for building a simple [`RequestBody`]
@@ -64,6 +120,23 @@ pub fn simple_request_body(request_body: SimpleRequestBody) -> RequestBody {
     }
 }
 
+/// Helper function for building the [`Responses`] an extractor rejects requests with
+///
+/// Takes pairs of a status code and a description.
+/// The responses' bodies are described as plain text.
+pub fn rejection_responses(rejections: impl IntoIterator<Item = (u16, &'static str)>) -> Responses {
+    simple_responses(
+        rejections
+            .into_iter()
+            .map(|(status_code, description)| SimpleResponse {
+                status_code: StatusCode::Code(status_code),
+                mime_type: mime::TEXT_PLAIN_UTF_8,
+                description: description.to_string(),
+                media_type: None,
+            }),
+    )
+}
+
 /// Describes the response for a specific status code
 pub struct SimpleRequestBody {
     /// The request body's mime type
@@ -93,6 +166,10 @@ pub mod macro_helper {
             Some(HandlerArgumentFns {
                 request_body: T::request_body,
                 parameters: T::parameters,
+                query_for_get_and_head: T::query_for_get_and_head,
+                security_schemes: T::security_schemes,
+                responses: T::responses,
+                rejection_responses: T::rejection_responses,
             })
         } else {
             None
@@ -0,0 +1,87 @@
+use indexmap::IndexMap;
+use openapiv3::Encoding;
+use openapiv3::MediaType;
+use openapiv3::ObjectType;
+use openapiv3::ReferenceOr;
+use openapiv3::RequestBody;
+use openapiv3::Responses;
+use openapiv3::Schema;
+use openapiv3::SchemaData;
+use openapiv3::SchemaKind;
+use openapiv3::Type;
+
+use crate::handler_argument::rejection_responses;
+use crate::handler_argument::HandlerArgument;
+use crate::handler_argument::ShouldBeHandlerArgument;
+use crate::internals::SchemaGenerator;
+use crate::multipart::FromMultipart;
+use crate::multipart::TypedMultipart;
+
+impl<T> ShouldBeHandlerArgument for TypedMultipart<T> {}
+impl<T: FromMultipart> HandlerArgument for TypedMultipart<T> {
+    fn request_body(gen: &mut SchemaGenerator) -> Option<RequestBody> {
+        let parts = T::parts(gen);
+
+        let mut object = ObjectType::default();
+        let mut encoding = IndexMap::new();
+        for part in parts {
+            if part.required {
+                object.required.push(part.name.to_string());
+            }
+            let schema = match (part.schema, part.description) {
+                (ReferenceOr::Item(mut schema), description) => {
+                    if let Some(description) = description {
+                        schema.schema_data.description = Some(description.to_string());
+                    }
+                    ReferenceOr::Item(Box::new(schema))
+                }
+                (ReferenceOr::Reference { reference }, None) => {
+                    ReferenceOr::Reference { reference }
+                }
+                // A reference can't have a description next to it in OpenAPI 3.0
+                (reference @ ReferenceOr::Reference { .. }, Some(description)) => {
+                    ReferenceOr::Item(Box::new(Schema {
+                        schema_data: SchemaData {
+                            description: Some(description.to_string()),
+                            ..Default::default()
+                        },
+                        schema_kind: SchemaKind::AllOf {
+                            all_of: vec![reference],
+                        },
+                    }))
+                }
+            };
+            object.properties.insert(part.name.to_string(), schema);
+            encoding.insert(
+                part.name.to_string(),
+                Encoding {
+                    content_type: Some(part.content_type.to_string()),
+                    ..Default::default()
+                },
+            );
+        }
+
+        Some(RequestBody {
+            content: IndexMap::from_iter([(
+                mime::MULTIPART_FORM_DATA.to_string(),
+                MediaType {
+                    schema: Some(ReferenceOr::Item(Schema {
+                        schema_data: Default::default(),
+                        schema_kind: SchemaKind::Type(Type::Object(object)),
+                    })),
+                    encoding,
+                    ..Default::default()
+                },
+            )]),
+            required: true,
+            ..Default::default()
+        })
+    }
+
+    fn rejection_responses(_gen: &mut SchemaGenerator) -> Responses {
+        rejection_responses([
+            (400, "Missing or invalid multipart part"),
+            (413, "The parts are too large"),
+        ])
+    }
+}
@@ -1,13 +1,24 @@
+use std::any::type_name;
+
+use log::warn;
+use openapiv3::Parameter;
+use openapiv3::ParameterData;
+use openapiv3::ParameterSchemaOrContent;
 use openapiv3::ReferenceOr;
 use openapiv3::RequestBody;
+use openapiv3::Responses;
 use openapiv3::Schema;
 use openapiv3::SchemaKind;
 use serde::de::DeserializeOwned;
 
+use crate::handler_argument::rejection_responses;
 use crate::handler_argument::simple_request_body;
 use crate::handler_argument::HandlerArgument;
 use crate::handler_argument::ShouldBeHandlerArgument;
 use crate::handler_argument::SimpleRequestBody;
+use crate::headers::FromHeaders;
+use crate::headers::HeaderLocation;
+use crate::headers::TypedHeaders;
 use crate::internals::SchemaGenerator;
 use crate::utils::SchemalessJson;
 
@@ -23,3 +34,52 @@ impl<T: DeserializeOwned> HandlerArgument for SchemalessJson<T> {
         }))
     }
 }
+
+impl<T> ShouldBeHandlerArgument for TypedHeaders<T> {}
+impl<T: FromHeaders> HandlerArgument for TypedHeaders<T> {
+    fn parameters(gen: &mut SchemaGenerator, _path: &[&str]) -> Vec<Parameter> {
+        let Some((mut obj, _)) = gen.generate_object::<T>() else {
+            warn!("Unsupported handler argument: {}", type_name::<Self>());
+            return Vec::new();
+        };
+
+        T::PARTS
+            .iter()
+            .filter_map(|part| {
+                let Some(schema) = obj.properties.shift_remove(part.property) else {
+                    warn!(
+                        "{} is missing the property `{}`",
+                        type_name::<T>(),
+                        part.property
+                    );
+                    return None;
+                };
+                let parameter_data = ParameterData {
+                    required: obj.required.iter().any(|name| name == part.property),
+                    name: part.name.to_string(),
+                    description: None,
+                    deprecated: None,
+                    format: ParameterSchemaOrContent::Schema(schema.unbox()),
+                    example: None,
+                    examples: Default::default(),
+                    explode: None,
+                    extensions: Default::default(),
+                };
+                Some(match part.location {
+                    HeaderLocation::Header => Parameter::Header {
+                        parameter_data,
+                        style: Default::default(),
+                    },
+                    HeaderLocation::Cookie => Parameter::Cookie {
+                        parameter_data,
+                        style: Default::default(),
+                    },
+                })
+            })
+            .collect()
+    }
+
+    fn rejection_responses(_gen: &mut SchemaGenerator) -> Responses {
+        rejection_responses([(400, "Missing or invalid header or cookie")])
+    }
+}
@@ -0,0 +1,237 @@
+//! Typed request headers and cookies
+//!
+//! Use [`#[derive(FromHeaders)]`](macro@crate::FromHeaders) together with [`JsonSchema`]
+//! on a struct and accept it in your handler wrapped in [`TypedHeaders`]:
+//!
+//! ```rust
+//! # use schemars::JsonSchema;
+//! # use swaggapi::FromHeaders;
+//! # use swaggapi::headers::TypedHeaders;
+//! #[derive(FromHeaders, JsonSchema)]
+//! struct Client {
+//!     /// The client's request id
+//!     #[header(name = "X-Request-Id")]
+//!     request_id: String,
+//!
+//!     /// The client's preferred page size
+//!     page_size: Option<u32>,
+//!
+//!     /// The client's session
+//!     #[cookie]
+//!     session: Option<String>,
+//! }
+//!
+//! # #[cfg(any(feature = "axum", feature = "actix"))]
+//! #[swaggapi::get("/client")]
+//! async fn client(client: TypedHeaders<Client>) -> String {
+//!     client.0.request_id
+//! }
+//! ```
+
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt;
+
+use schemars::JsonSchema;
+
+/// Extractor for request headers and cookies parsed into `T`
+///
+/// `T` is usually implemented using [`#[derive(FromHeaders)]`](macro@crate::FromHeaders).
+#[derive(Debug)]
+pub struct TypedHeaders<T>(pub T);
+
+/// A type which can be constructed from a request's headers and cookies
+///
+/// This trait should be implemented using [`#[derive(FromHeaders)]`](macro@crate::FromHeaders).
+///
+/// The parameters' schemas are taken from `Self`'s [`JsonSchema`]
+/// which has to be an object containing a property for every part.
+pub trait FromHeaders: JsonSchema + Sized {
+    /// The headers and cookies `Self` is constructed from
+    const PARTS: &'static [HeaderPart];
+
+    /// Construct `Self` from the request's headers and cookies
+    fn from_headers(headers: &RequestHeaders) -> Result<Self, HeaderError>;
+}
+
+/// Description of a single header or cookie in a [`FromHeaders`] type
+#[derive(Copy, Clone, Debug)]
+pub struct HeaderPart {
+    /// The name of the property in the type's schema
+    pub property: &'static str,
+
+    /// The header's or cookie's name
+    pub name: &'static str,
+
+    /// Is the part a header or a cookie?
+    pub location: HeaderLocation,
+}
+
+/// The location of a [`HeaderPart`]
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub enum HeaderLocation {
+    /// A header
+    Header,
+    /// A cookie
+    Cookie,
+}
+
+/// A field in a [`FromHeaders`] type
+pub trait HeaderField: Sized {
+    /// Construct the field from a header's or cookie's value
+    fn from_value(name: &'static str, value: Option<&str>) -> Result<Self, HeaderError>;
+}
+
+/// A request's headers and cookies
+///
+/// Header names are case-insensitive, cookie names are not.
+/// If a header or cookie appears multiple times, only its first value is kept.
+#[derive(Debug, Default)]
+pub struct RequestHeaders {
+    headers: HashMap<String, String>,
+    cookies: HashMap<String, String>,
+}
+
+impl RequestHeaders {
+    /// Adds a header
+    ///
+    /// `Cookie` headers are split into their cookies.
+    pub fn insert(&mut self, name: &str, value: &str) {
+        let name = name.to_ascii_lowercase();
+        if name == "cookie" {
+            for cookie in value.split(';') {
+                if let Some((name, value)) = cookie.split_once('=') {
+                    self.cookies
+                        .entry(name.trim().to_string())
+                        .or_insert_with(|| value.trim().trim_matches('"').to_string());
+                }
+            }
+        }
+        self.headers
+            .entry(name)
+            .or_insert_with(|| value.to_string());
+    }
+
+    /// Gets the value of a header or cookie
+    pub fn get(&self, location: HeaderLocation, name: &str) -> Option<&str> {
+        match location {
+            HeaderLocation::Header => self.headers.get(&name.to_ascii_lowercase()),
+            HeaderLocation::Cookie => self.cookies.get(name),
+        }
+        .map(String::as_str)
+    }
+
+    /// Constructs a field from a header or cookie
+    pub fn parse<F: HeaderField>(&self, part: &HeaderPart) -> Result<F, HeaderError> {
+        F::from_value(part.name, self.get(part.location, part.name))
+    }
+}
+
+/// Error produced while constructing a [`FromHeaders`] type
+#[derive(Debug)]
+pub enum HeaderError {
+    /// A required header or cookie is missing
+    Missing(&'static str),
+
+    /// A header or cookie could not be parsed
+    Invalid(&'static str),
+}
+
+impl fmt::Display for HeaderError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            HeaderError::Missing(name) => write!(f, "Missing header or cookie `{name}`"),
+            HeaderError::Invalid(name) => write!(f, "Invalid header or cookie `{name}`"),
+        }
+    }
+}
+
+impl Error for HeaderError {}
+
+macro_rules! impl_header_field {
+    ($($ty:ty),+) => {$(
+        impl HeaderField for $ty {
+            fn from_value(name: &'static str, value: Option<&str>) -> Result<Self, HeaderError> {
+                value
+                    .ok_or(HeaderError::Missing(name))?
+                    .parse()
+                    .map_err(|_| HeaderError::Invalid(name))
+            }
+        }
+    )+};
+}
+impl_header_field!(
+    String, bool, char, u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize, f32, f64
+);
+
+impl<F: HeaderField> HeaderField for Option<F> {
+    fn from_value(name: &'static str, value: Option<&str>) -> Result<Self, HeaderError> {
+        value
+            .map(|value| F::from_value(name, Some(value)))
+            .transpose()
+    }
+}
+
+#[cfg(feature = "actix")]
+const _: () = {
+    use std::future::ready;
+    use std::future::Ready;
+
+    use actix_web::dev::Payload;
+    use actix_web::FromRequest;
+    use actix_web::HttpRequest;
+    use actix_web::ResponseError;
+
+    impl ResponseError for HeaderError {
+        fn status_code(&self) -> actix_web::http::StatusCode {
+            actix_web::http::StatusCode::BAD_REQUEST
+        }
+    }
+
+    impl<T: FromHeaders> FromRequest for TypedHeaders<T> {
+        type Error = HeaderError;
+        type Future = Ready<Result<Self, Self::Error>>;
+
+        fn from_request(req: &HttpRequest, _payload: &mut Payload) -> Self::Future {
+            let mut headers = RequestHeaders::default();
+            for (name, value) in req.headers() {
+                if let Ok(value) = value.to_str() {
+                    headers.insert(name.as_str(), value);
+                }
+            }
+            ready(T::from_headers(&headers).map(TypedHeaders))
+        }
+    }
+};
+
+#[cfg(feature = "axum")]
+const _: () = {
+    use axum::extract::FromRequestParts;
+    use axum::http::request::Parts;
+    use axum::http::StatusCode;
+    use axum::response::IntoResponse;
+    use axum::response::Response;
+
+    impl IntoResponse for HeaderError {
+        fn into_response(self) -> Response {
+            (StatusCode::BAD_REQUEST, self.to_string()).into_response()
+        }
+    }
+
+    impl<T: FromHeaders, S: Sync> FromRequestParts<S> for TypedHeaders<T> {
+        type Rejection = HeaderError;
+
+        async fn from_request_parts(
+            parts: &mut Parts,
+            _state: &S,
+        ) -> Result<Self, Self::Rejection> {
+            let mut headers = RequestHeaders::default();
+            for (name, value) in &parts.headers {
+                if let Ok(value) = value.to_str() {
+                    headers.insert(name.as_str(), value);
+                }
+            }
+            T::from_headers(&headers).map(TypedHeaders)
+        }
+    }
+};
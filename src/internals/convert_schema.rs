@@ -1,288 +1,364 @@
 use ::openapiv3 as openapi;
 use ::schemars::schema as schemars;
-use log::warn;
+use indexmap::IndexMap;
 use serde::de::value::StrDeserializer;
 use serde::de::DeserializeOwned;
 
+use crate::diagnostics::ConversionDiagnostic;
+use crate::diagnostics::ConversionDiagnosticKind;
+
 /// Convert a [schemars](::schemars)'s [`Schema`](::schemars::schema::Schema) to a [openapiv3]'s [`Schema`](openapiv3::Schema)
+///
+/// Anything which can't be converted is silently dropped.
+/// Use [`convert_schema_with_diagnostics`] to find out what was lost.
 pub fn convert_schema(schema: schemars::Schema) -> openapi::ReferenceOr<openapi::Schema> {
-    match schema {
-        schemars::Schema::Object(object) => convert_schema_object(object),
-        schemars::Schema::Bool(boolean) => {
-            // Empty schema which would match everything
-            let mut schema = openapi::ReferenceOr::Item(openapi::Schema {
-                schema_data: Default::default(),
-                schema_kind: openapi::SchemaKind::Any(openapi::AnySchema::default()),
-            });
+    convert_schema_with_diagnostics(schema, "", &mut Vec::new())
+}
 
-            if !boolean {
-                // Wrap with not to match nothing
-                schema = openapi::ReferenceOr::Item(openapi::Schema {
-                    schema_data: Default::default(),
-                    schema_kind: openapi::SchemaKind::Not {
-                        not: Box::new(schema),
-                    },
-                });
-            }
+/// Convert a [schemars](::schemars)'s [`Schema`](::schemars::schema::Schema) to a [openapiv3]'s [`Schema`](openapiv3::Schema)
+///
+/// Anything which can't be converted is reported by pushing a [`ConversionDiagnostic`] to `diagnostics`.
+/// The `name` is used to identify the schema in those diagnostics.
+pub fn convert_schema_with_diagnostics(
+    schema: schemars::Schema,
+    name: &str,
+    diagnostics: &mut Vec<ConversionDiagnostic>,
+) -> openapi::ReferenceOr<openapi::Schema> {
+    Converter {
+        schema: name,
+        pointer: String::new(),
+        diagnostics,
+    }
+    .convert_schema(schema)
+}
+
+/// State of a single [`convert_schema_with_diagnostics`] call
+struct Converter<'a> {
+    /// The name of the schema being converted
+    schema: &'a str,
+
+    /// JSON pointer to the sub schema currently being converted
+    pointer: String,
 
-            schema
+    /// The diagnostics collected so far
+    diagnostics: &'a mut Vec<ConversionDiagnostic>,
+}
+
+impl Converter<'_> {
+    /// Report a diagnostic at the current position
+    fn report(&mut self, kind: ConversionDiagnosticKind) {
+        let diagnostic = ConversionDiagnostic {
+            schema: self.schema.to_string(),
+            pointer: self.pointer.clone(),
+            kind,
+        };
+        if !self.diagnostics.contains(&diagnostic) {
+            self.diagnostics.push(diagnostic);
         }
     }
-}
 
-fn convert_schema_object(schema: schemars::SchemaObject) -> openapi::ReferenceOr<openapi::Schema> {
-    let schemars::SchemaObject {
-        metadata,
-        instance_type,
-        format,
-        enum_values,
-        const_value,
-        subschemas,
-        number,
-        string,
-        array,
-        object,
-        reference,
-        extensions,
-    } = schema;
-    if let Some(reference) = reference {
-        return openapi::ReferenceOr::Reference { reference };
+    /// Run `func` with `segments` appended to the current position
+    fn nested<T>(&mut self, segments: &[&str], func: impl FnOnce(&mut Self) -> T) -> T {
+        let len = self.pointer.len();
+        for segment in segments {
+            self.pointer.push('/');
+            self.pointer
+                .push_str(&segment.replace('~', "~0").replace('/', "~1"));
+        }
+        let output = func(self);
+        self.pointer.truncate(len);
+        output
     }
 
-    if const_value.is_some() {
-        warn!("Can't convert `const_value`");
+    /// Convert a list of sub schemas stored under `keyword`
+    fn convert_schemas(
+        &mut self,
+        keyword: &str,
+        schemas: Vec<schemars::Schema>,
+    ) -> Vec<openapi::ReferenceOr<openapi::Schema>> {
+        schemas
+            .into_iter()
+            .enumerate()
+            .map(|(index, schema)| {
+                self.nested(&[keyword, &index.to_string()], |this| {
+                    this.convert_schema(schema)
+                })
+            })
+            .collect()
     }
 
-    let mut schema_data = metadata
-        .map(|metadata| {
-            let schemars::Metadata {
-                id: _, // TODO maybe external_docs
-                title,
-                description,
-                default,
-                deprecated,
-                read_only,
-                write_only,
-                examples,
-            } = *metadata;
-            if examples.len() > 1 {
-                warn!("Only the first of the schema's examples will be used");
-            }
-            openapi::SchemaData {
-                nullable: false,
-                read_only,
-                write_only,
-                deprecated,
-                example: examples.into_iter().next(),
-                title,
-                description,
-                default,
-                extensions: extensions.into_iter().collect(),
-                // always empty because unique to openapi
-                external_docs: None,
-                discriminator: None,
+    fn convert_schema(
+        &mut self,
+        schema: schemars::Schema,
+    ) -> openapi::ReferenceOr<openapi::Schema> {
+        match schema {
+            schemars::Schema::Object(object) => self.convert_schema_object(object),
+            schemars::Schema::Bool(boolean) => {
+                // Empty schema which would match everything
+                let mut schema = openapi::ReferenceOr::Item(openapi::Schema {
+                    schema_data: Default::default(),
+                    schema_kind: openapi::SchemaKind::Any(openapi::AnySchema::default()),
+                });
+
+                if !boolean {
+                    // Wrap with not to match nothing
+                    schema = openapi::ReferenceOr::Item(openapi::Schema {
+                        schema_data: Default::default(),
+                        schema_kind: openapi::SchemaKind::Not {
+                            not: Box::new(schema),
+                        },
+                    });
+                }
+
+                schema
             }
-        })
-        .unwrap_or_default();
-
-    let mut kinds = Vec::new();
-    if let Some(subschemas) = subschemas {
-        let schemars::SubschemaValidation {
-            all_of,
-            any_of,
-            one_of,
-            not,
-            if_schema,
-            then_schema,
-            else_schema,
-        } = *subschemas;
-        if let Some(all_of) = all_of {
-            kinds.push(openapi::SchemaKind::AllOf {
-                all_of: all_of.into_iter().map(convert_schema).collect(),
-            });
-        }
-        if let Some(any_of) = any_of {
-            kinds.push(openapi::SchemaKind::AnyOf {
-                any_of: any_of.into_iter().map(convert_schema).collect(),
-            });
-        }
-        if let Some(one_of) = one_of {
-            kinds.push(openapi::SchemaKind::OneOf {
-                one_of: one_of.into_iter().map(convert_schema).collect(),
-            });
-        }
-        if let Some(not) = not {
-            kinds.push(openapi::SchemaKind::Not {
-                not: Box::new(convert_schema(*not)),
-            });
-        }
-        if if_schema.is_some() {
-            warn!("Can't convert `if_schema`");
-        }
-        if then_schema.is_some() {
-            warn!("Can't convert `then_schema`");
-        }
-        if else_schema.is_some() {
-            warn!("Can't convert `else_schema`");
         }
     }
-    if let Some(instance_type) = instance_type {
-        let InstanceTypes {
-            is_null,
-            is_boolean,
-            is_object,
-            is_array,
-            is_number,
-            is_string,
-            is_integer,
-        } = InstanceTypes::parse(instance_type);
-
-        let mut enum_values = enum_values.unwrap_or_default();
-        let mut types = Vec::new();
-        if is_boolean {
-            types.push(openapi::Type::Boolean(convert_boolean_type(
-                &mut enum_values,
-            )));
-        }
-        if is_object {
-            types.push(openapi::Type::Object(convert_object_type(
-                object,
-                &mut enum_values,
-            )));
-        }
-        if is_array {
-            types.extend(
-                convert_array_type(array, &mut enum_values)
-                    .into_iter()
-                    .map(openapi::Type::Array),
-            );
-        }
-        if is_number {
-            types.push(openapi::Type::Number(convert_number_type(
-                number.clone(),
-                format.as_deref(),
-                &mut enum_values,
-            )));
-        }
-        if is_string {
-            types.push(openapi::Type::String(convert_string_type(
-                string,
-                format.as_deref(),
-                &mut enum_values,
-            )));
-        }
-        if is_integer {
-            types.push(openapi::Type::Integer(convert_integer_type(
-                number,
-                format.as_deref(),
-                &mut enum_values,
-            )));
-        }
 
-        if matches!(types.len(), 0 | 1) {
-            schema_data.nullable = is_null;
-        }
-        match types.len() {
-            0 => {}
-            1 => kinds.push(openapi::SchemaKind::Type(
-                types.pop().expect("Length should be one"),
-            )),
-            _ => kinds.push(openapi::SchemaKind::OneOf {
-                one_of: types
-                    .into_iter()
-                    .map(|typ| {
-                        openapi::ReferenceOr::Item(openapi::Schema {
-                            schema_data: openapi::SchemaData::default(),
-                            schema_kind: openapi::SchemaKind::Type(typ),
-                        })
-                    })
-                    .collect(),
-            }),
+    fn convert_schema_object(
+        &mut self,
+        schema: schemars::SchemaObject,
+    ) -> openapi::ReferenceOr<openapi::Schema> {
+        let schemars::SchemaObject {
+            metadata,
+            instance_type,
+            format,
+            enum_values,
+            const_value,
+            subschemas,
+            number,
+            string,
+            array,
+            object,
+            reference,
+            extensions,
+        } = schema;
+        if let Some(reference) = reference {
+            return openapi::ReferenceOr::Reference { reference };
         }
-    }
 
-    openapi::ReferenceOr::Item(match kinds.len() {
-        0 => openapi::Schema {
-            schema_data,
-            schema_kind: openapi::SchemaKind::Any(openapi::AnySchema::default()),
-        },
-        1 => openapi::Schema {
-            schema_data,
-            schema_kind: kinds.pop().expect("Length should be one"),
-        },
-        _ => openapi::Schema {
-            schema_data,
-            schema_kind: openapi::SchemaKind::AllOf {
-                all_of: kinds
-                    .into_iter()
-                    .map(|schema_kind| {
-                        openapi::ReferenceOr::Item(openapi::Schema {
-                            schema_data: openapi::SchemaData::default(),
-                            schema_kind,
-                        })
-                    })
-                    .collect(),
-            },
-        },
-    })
-}
-
-#[derive(Default)]
-struct InstanceTypes {
-    is_null: bool,
-    is_boolean: bool,
-    is_object: bool,
-    is_array: bool,
-    is_number: bool,
-    is_string: bool,
-    is_integer: bool,
-}
-impl InstanceTypes {
-    pub fn parse(input: schemars::SingleOrVec<schemars::InstanceType>) -> Self {
-        let mut output = Self::default();
-        let mut set = |instance_type: schemars::InstanceType| match instance_type {
-            schemars::InstanceType::Null => {
-                output.is_null = true;
-                if output.is_null {
-                    warn!("Instance type `Null` is specified multiple times");
+        // `const` is equivalent to an `enum` with a single value
+        let mut instance_type = instance_type;
+        let enum_values = match const_value {
+            None => enum_values,
+            Some(value) => {
+                if instance_type.is_none() {
+                    instance_type = Some(instance_type_of(&value).into());
                 }
-            }
-            schemars::InstanceType::Boolean => {
-                output.is_boolean = true;
-                if output.is_null {
-                    warn!("Instance type `Boolean` is specified multiple times");
+                match enum_values {
+                    Some(values) if !values.contains(&value) => {
+                        self.report(ConversionDiagnosticKind::UnsupportedKeyword("const"));
+                        Some(values)
+                    }
+                    _ => Some(vec![value]),
                 }
             }
-            schemars::InstanceType::Object => {
-                output.is_object = true;
-                if output.is_null {
-                    warn!("Instance type `Object` is specified multiple times");
+        };
+
+        let mut schema_data = metadata
+            .map(|metadata| {
+                let schemars::Metadata {
+                    id: _, // TODO maybe external_docs
+                    title,
+                    description,
+                    default,
+                    deprecated,
+                    read_only,
+                    write_only,
+                    examples,
+                } = *metadata;
+                if examples.len() > 1 {
+                    self.report(ConversionDiagnosticKind::MultipleExamples);
                 }
-            }
-            schemars::InstanceType::Array => {
-                output.is_array = true;
-                if output.is_null {
-                    warn!("Instance type `Array` is specified multiple times");
+                openapi::SchemaData {
+                    nullable: false,
+                    read_only,
+                    write_only,
+                    deprecated,
+                    example: examples.into_iter().next(),
+                    title,
+                    description,
+                    default,
+                    extensions: extensions.into_iter().collect(),
+                    // always empty because unique to openapi
+                    external_docs: None,
+                    discriminator: None,
                 }
+            })
+            .unwrap_or_default();
+
+        let mut kinds = Vec::new();
+        let mut discriminator = None;
+        if let Some(subschemas) = subschemas {
+            let schemars::SubschemaValidation {
+                all_of,
+                any_of,
+                one_of,
+                not,
+                if_schema,
+                then_schema,
+                else_schema,
+            } = *subschemas;
+            if let Some(all_of) = all_of {
+                kinds.push(openapi::SchemaKind::AllOf {
+                    all_of: self.convert_schemas("allOf", all_of),
+                });
             }
-            schemars::InstanceType::Number => {
-                output.is_number = true;
-                if output.is_null {
-                    warn!("Instance type `Number` is specified multiple times");
-                }
+            if let Some(any_of) = any_of {
+                kinds.push(openapi::SchemaKind::AnyOf {
+                    any_of: self.convert_schemas("anyOf", any_of),
+                });
             }
-            schemars::InstanceType::String => {
-                output.is_string = true;
-                if output.is_null {
-                    warn!("Instance type `String` is specified multiple times");
-                }
+            if let Some(one_of) = one_of {
+                let one_of = self.convert_schemas("oneOf", one_of);
+                discriminator = find_discriminator(&one_of);
+                kinds.push(openapi::SchemaKind::OneOf { one_of });
             }
-            schemars::InstanceType::Integer => {
-                output.is_integer = true;
-                if output.is_null {
-                    warn!("Instance type `Integer` is specified multiple times");
-                }
+            if let Some(not) = not {
+                kinds.push(openapi::SchemaKind::Not {
+                    not: Box::new(self.nested(&["not"], |this| this.convert_schema(*not))),
+                });
+            }
+            if if_schema.is_some() {
+                self.report(ConversionDiagnosticKind::UnsupportedKeyword("if"));
+            }
+            if then_schema.is_some() {
+                self.report(ConversionDiagnosticKind::UnsupportedKeyword("then"));
+            }
+            if else_schema.is_some() {
+                self.report(ConversionDiagnosticKind::UnsupportedKeyword("else"));
+            }
+        }
+        if let Some(instance_type) = instance_type {
+            let InstanceTypes {
+                is_null,
+                is_boolean,
+                is_object,
+                is_array,
+                is_number,
+                is_string,
+                is_integer,
+            } = self.parse_instance_types(instance_type);
+
+            let mut enum_values = enum_values.unwrap_or_default();
+            let mut types = Vec::new();
+            if is_boolean {
+                types.push(openapi::Type::Boolean(convert_boolean_type(
+                    &mut enum_values,
+                )));
+            }
+            if is_object {
+                types.push(openapi::Type::Object(
+                    self.convert_object_type(object, &mut enum_values),
+                ));
+            }
+            if is_array {
+                types.extend(
+                    self.convert_array_type(array, &mut enum_values)
+                        .into_iter()
+                        .map(openapi::Type::Array),
+                );
+            }
+            if is_number {
+                types.push(openapi::Type::Number(self.convert_number_type(
+                    number.clone(),
+                    format.as_deref(),
+                    &mut enum_values,
+                )));
+            }
+            if is_string {
+                types.push(openapi::Type::String(self.convert_string_type(
+                    string,
+                    format.as_deref(),
+                    &mut enum_values,
+                )));
+            }
+            if is_integer {
+                types.push(openapi::Type::Integer(self.convert_integer_type(
+                    number,
+                    format.as_deref(),
+                    &mut enum_values,
+                )));
+            }
+
+            if matches!(types.len(), 0 | 1) {
+                schema_data.nullable = is_null;
+            }
+            match types.len() {
+                0 => {}
+                1 => kinds.push(openapi::SchemaKind::Type(
+                    types.pop().expect("Length should be one"),
+                )),
+                _ => kinds.push(openapi::SchemaKind::OneOf {
+                    one_of: types
+                        .into_iter()
+                        .map(|typ| {
+                            openapi::ReferenceOr::Item(openapi::Schema {
+                                schema_data: openapi::SchemaData::default(),
+                                schema_kind: openapi::SchemaKind::Type(typ),
+                            })
+                        })
+                        .collect(),
+                }),
             }
+        }
+
+        openapi::ReferenceOr::Item(match kinds.len() {
+            0 => openapi::Schema {
+                schema_data,
+                schema_kind: openapi::SchemaKind::Any(openapi::AnySchema::default()),
+            },
+            1 => openapi::Schema {
+                schema_data: openapi::SchemaData {
+                    discriminator,
+                    ..schema_data
+                },
+                schema_kind: kinds.pop().expect("Length should be one"),
+            },
+            _ => openapi::Schema {
+                schema_data,
+                schema_kind: openapi::SchemaKind::AllOf {
+                    all_of: kinds
+                        .into_iter()
+                        .map(|schema_kind| {
+                            openapi::ReferenceOr::Item(openapi::Schema {
+                                schema_data: openapi::SchemaData {
+                                    discriminator: matches!(
+                                        schema_kind,
+                                        openapi::SchemaKind::OneOf { .. }
+                                    )
+                                    .then(|| discriminator.take())
+                                    .flatten(),
+                                    ..Default::default()
+                                },
+                                schema_kind,
+                            })
+                        })
+                        .collect(),
+                },
+            },
+        })
+    }
+
+    fn parse_instance_types(
+        &mut self,
+        input: schemars::SingleOrVec<schemars::InstanceType>,
+    ) -> InstanceTypes {
+        let mut output = InstanceTypes::default();
+        let mut set = |instance_type: schemars::InstanceType| {
+            let (is_set, name) = match instance_type {
+                schemars::InstanceType::Null => (&mut output.is_null, "null"),
+                schemars::InstanceType::Boolean => (&mut output.is_boolean, "boolean"),
+                schemars::InstanceType::Object => (&mut output.is_object, "object"),
+                schemars::InstanceType::Array => (&mut output.is_array, "array"),
+                schemars::InstanceType::Number => (&mut output.is_number, "number"),
+                schemars::InstanceType::String => (&mut output.is_string, "string"),
+                schemars::InstanceType::Integer => (&mut output.is_integer, "integer"),
+            };
+            if *is_set {
+                self.report(ConversionDiagnosticKind::DuplicateInstanceType(name));
+            }
+            *is_set = true;
         };
         match input {
             schemars::SingleOrVec::Single(instance_type) => set(*instance_type),
@@ -290,219 +366,371 @@ impl InstanceTypes {
         }
         output
     }
-}
 
-fn convert_object_type(
-    input: Option<Box<schemars::ObjectValidation>>,
-    enums: &mut [serde_json::Value],
-) -> openapi::ObjectType {
-    let schemars::ObjectValidation {
-        max_properties,
-        min_properties,
-        required,
-        properties,
-        pattern_properties,
-        additional_properties,
-        property_names,
-    } = *input.unwrap_or_default();
-
-    if !pattern_properties.is_empty() {
-        warn!("Can't convert `pattern_properties`");
-    }
-    if property_names.is_some() {
-        warn!("Can't convert `property_names`");
-    }
-    if enums.iter().any(|value| value.is_object()) {
-        warn!("Can't convert `enum` for type `object`");
-    }
+    fn convert_object_type(
+        &mut self,
+        input: Option<Box<schemars::ObjectValidation>>,
+        enums: &mut [serde_json::Value],
+    ) -> openapi::ObjectType {
+        let schemars::ObjectValidation {
+            max_properties,
+            min_properties,
+            required,
+            properties,
+            pattern_properties,
+            additional_properties,
+            property_names,
+        } = *input.unwrap_or_default();
+
+        if !pattern_properties.is_empty() {
+            self.report(ConversionDiagnosticKind::UnsupportedKeyword(
+                "patternProperties",
+            ));
+        }
+        if property_names.is_some() {
+            self.report(ConversionDiagnosticKind::UnsupportedKeyword(
+                "propertyNames",
+            ));
+        }
+        if enums.iter().any(|value| value.is_object()) {
+            self.report(ConversionDiagnosticKind::UnsupportedEnum("object"));
+        }
 
-    openapi::ObjectType {
-        properties: properties
-            .into_iter()
-            .map(|(key, schema)| (key, box_reference_or(convert_schema(schema))))
-            .collect(),
-        required: required.into_iter().collect(),
-        additional_properties: additional_properties.map(|additional_properties| {
-            match *additional_properties {
-                schemars::Schema::Bool(boolean) => openapi::AdditionalProperties::Any(boolean),
-                schemars::Schema::Object(object) => {
-                    openapi::AdditionalProperties::Schema(Box::new(convert_schema_object(object)))
+        openapi::ObjectType {
+            properties: properties
+                .into_iter()
+                .map(|(key, schema)| {
+                    let schema =
+                        self.nested(&["properties", &key], |this| this.convert_schema(schema));
+                    (key, box_reference_or(schema))
+                })
+                .collect(),
+            required: required.into_iter().collect(),
+            additional_properties: additional_properties.map(|additional_properties| {
+                match *additional_properties {
+                    schemars::Schema::Bool(boolean) => openapi::AdditionalProperties::Any(boolean),
+                    schemars::Schema::Object(object) => openapi::AdditionalProperties::Schema(
+                        Box::new(self.nested(&["additionalProperties"], |this| {
+                            this.convert_schema_object(object)
+                        })),
+                    ),
                 }
-            }
-        }),
-        min_properties: min_properties.map(convert_u32),
-        max_properties: max_properties.map(convert_u32),
-    }
-}
-
-fn convert_array_type(
-    input: Option<Box<schemars::ArrayValidation>>,
-    enums: &mut [serde_json::Value],
-) -> Vec<openapi::ArrayType> {
-    let schemars::ArrayValidation {
-        items,
-        additional_items,
-        max_items,
-        min_items,
-        unique_items,
-        contains,
-    } = *input.unwrap_or_default();
-
-    if additional_items.is_some() {
-        warn!("Can't convert `additional_items`");
-    }
-    let max_items = max_items.map(convert_u32);
-    let min_items = min_items.map(convert_u32);
-    let unique_items = unique_items.unwrap_or(false);
-    if contains.is_some() {
-        warn!("Can't convert `contains`");
-    }
-    if enums.iter().any(|value| value.is_array()) {
-        warn!("Can't convert `enum` for type `array`");
+            }),
+            min_properties: min_properties.map(|value| self.convert_u32(value)),
+            max_properties: max_properties.map(|value| self.convert_u32(value)),
+        }
     }
 
-    let mut arrays = Vec::new();
-    let mut push_array = |item: schemars::Schema| {
-        arrays.push(openapi::ArrayType {
-            items: Some(box_reference_or(convert_schema(item))),
-            min_items,
+    fn convert_array_type(
+        &mut self,
+        input: Option<Box<schemars::ArrayValidation>>,
+        enums: &mut [serde_json::Value],
+    ) -> Vec<openapi::ArrayType> {
+        let schemars::ArrayValidation {
+            items,
+            additional_items,
             max_items,
+            min_items,
             unique_items,
-        })
-    };
-    match items {
-        None => push_array(schemars::Schema::Bool(true)),
-        Some(schemars::SingleOrVec::Single(item)) => push_array(*item),
-        Some(schemars::SingleOrVec::Vec(items)) => items.into_iter().for_each(push_array),
-    }
-    arrays
-}
+            contains,
+        } = *input.unwrap_or_default();
 
-fn convert_number_type(
-    input: Option<Box<schemars::NumberValidation>>,
-    format: Option<&str>,
-    enums: &mut [serde_json::Value],
-) -> openapi::NumberType {
-    let schemars::NumberValidation {
-        multiple_of,
-        mut maximum,
-        exclusive_maximum,
-        mut minimum,
-        exclusive_minimum,
-    } = *input.unwrap_or_default();
-
-    if let Some(exclusive_maximum) = exclusive_maximum {
-        if maximum.is_some() {
-            warn!("`maximum` and `exclusive_maximum` are both set");
+        if additional_items.is_some() {
+            self.report(ConversionDiagnosticKind::UnsupportedKeyword(
+                "additionalItems",
+            ));
         }
-        maximum = Some(exclusive_maximum);
-    }
-    if let Some(exclusive_minimum) = exclusive_minimum {
-        if minimum.is_some() {
-            warn!("`minimum` and `exclusive_minimum` are both set");
+        let max_items = max_items.map(|value| self.convert_u32(value));
+        let min_items = min_items.map(|value| self.convert_u32(value));
+        let unique_items = unique_items.unwrap_or(false);
+        if contains.is_some() {
+            self.report(ConversionDiagnosticKind::UnsupportedKeyword("contains"));
+        }
+        if enums.iter().any(|value| value.is_array()) {
+            self.report(ConversionDiagnosticKind::UnsupportedEnum("array"));
         }
-        minimum = Some(exclusive_minimum);
-    }
 
-    let mut enumeration = Vec::new();
-    for value in enums {
-        match value {
-            serde_json::Value::Null => enumeration.push(None),
-            serde_json::Value::Number(number) => {
-                if let Some(number) = number.as_f64() {
-                    enumeration.push(Some(number));
+        let mut arrays = Vec::new();
+        let mut push_array = |this: &mut Self, item: schemars::Schema, segments: &[&str]| {
+            arrays.push(openapi::ArrayType {
+                items: Some(box_reference_or(
+                    this.nested(segments, |this| this.convert_schema(item)),
+                )),
+                min_items,
+                max_items,
+                unique_items,
+            })
+        };
+        match items {
+            None => push_array(self, schemars::Schema::Bool(true), &["items"]),
+            Some(schemars::SingleOrVec::Single(item)) => push_array(self, *item, &["items"]),
+            Some(schemars::SingleOrVec::Vec(items)) => {
+                for (index, item) in items.into_iter().enumerate() {
+                    push_array(self, item, &["items", &index.to_string()]);
                 }
             }
-            _ => {}
         }
+        arrays
     }
 
-    openapi::NumberType {
-        format: convert_format(format),
-        multiple_of,
-        exclusive_minimum: exclusive_minimum.is_some(),
-        exclusive_maximum: exclusive_maximum.is_some(),
-        minimum,
-        maximum,
-        enumeration,
+    fn convert_number_type(
+        &mut self,
+        input: Option<Box<schemars::NumberValidation>>,
+        format: Option<&str>,
+        enums: &mut [serde_json::Value],
+    ) -> openapi::NumberType {
+        let schemars::NumberValidation {
+            multiple_of,
+            mut maximum,
+            exclusive_maximum,
+            mut minimum,
+            exclusive_minimum,
+        } = *input.unwrap_or_default();
+
+        if let Some(exclusive_maximum) = exclusive_maximum {
+            if maximum.is_some() {
+                self.report(ConversionDiagnosticKind::ConflictingBounds("maximum"));
+            }
+            maximum = Some(exclusive_maximum);
+        }
+        if let Some(exclusive_minimum) = exclusive_minimum {
+            if minimum.is_some() {
+                self.report(ConversionDiagnosticKind::ConflictingBounds("minimum"));
+            }
+            minimum = Some(exclusive_minimum);
+        }
+
+        let mut enumeration = Vec::new();
+        for value in enums {
+            match value {
+                serde_json::Value::Null => enumeration.push(None),
+                serde_json::Value::Number(number) => {
+                    if let Some(number) = number.as_f64() {
+                        enumeration.push(Some(number));
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        openapi::NumberType {
+            format: convert_format(format),
+            multiple_of,
+            exclusive_minimum: exclusive_minimum.is_some(),
+            exclusive_maximum: exclusive_maximum.is_some(),
+            minimum,
+            maximum,
+            enumeration,
+        }
     }
-}
 
-fn convert_string_type(
-    input: Option<Box<schemars::StringValidation>>,
-    format: Option<&str>,
-    enums: &mut [serde_json::Value],
-) -> openapi::StringType {
-    let schemars::StringValidation {
-        max_length,
-        min_length,
-        pattern,
-    } = *input.unwrap_or_default();
+    fn convert_string_type(
+        &mut self,
+        input: Option<Box<schemars::StringValidation>>,
+        format: Option<&str>,
+        enums: &mut [serde_json::Value],
+    ) -> openapi::StringType {
+        let schemars::StringValidation {
+            max_length,
+            min_length,
+            pattern,
+        } = *input.unwrap_or_default();
+
+        let mut enumeration = Vec::new();
+        for value in enums {
+            match value {
+                serde_json::Value::Null => enumeration.push(None),
+                serde_json::Value::String(string) => enumeration.push(Some(std::mem::take(string))),
+                _ => {}
+            }
+        }
 
-    let mut enumeration = Vec::new();
-    for value in enums {
-        match value {
-            serde_json::Value::Null => enumeration.push(None),
-            serde_json::Value::String(string) => enumeration.push(Some(std::mem::take(string))),
-            _ => {}
+        openapi::StringType {
+            format: convert_format(format),
+            pattern,
+            enumeration,
+            min_length: min_length.map(|value| self.convert_u32(value)),
+            max_length: max_length.map(|value| self.convert_u32(value)),
         }
     }
 
-    openapi::StringType {
-        format: convert_format(format),
-        pattern,
-        enumeration,
-        min_length: min_length.map(convert_u32),
-        max_length: max_length.map(convert_u32),
+    fn convert_integer_type(
+        &mut self,
+        input: Option<Box<schemars::NumberValidation>>,
+        format: Option<&str>,
+        enums: &mut [serde_json::Value],
+    ) -> openapi::IntegerType {
+        let openapi::NumberType {
+            format: _,
+            multiple_of,
+            exclusive_minimum,
+            exclusive_maximum,
+            minimum,
+            maximum,
+            enumeration: _,
+        } = self.convert_number_type(input, None, &mut []);
+
+        let mut enumeration = Vec::new();
+        for value in enums {
+            match value {
+                serde_json::Value::Null => enumeration.push(None),
+                serde_json::Value::Number(number) => {
+                    if let Some(number) = number.as_i64() {
+                        enumeration.push(Some(number));
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        openapi::IntegerType {
+            format: convert_format(format),
+            multiple_of: multiple_of.map(|value| self.convert_f64("multipleOf", value)),
+            exclusive_minimum,
+            exclusive_maximum,
+            minimum: minimum.map(|value| self.convert_f64("minimum", value)),
+            maximum: maximum.map(|value| self.convert_f64("maximum", value)),
+            enumeration,
+        }
     }
-}
 
-fn convert_integer_type(
-    input: Option<Box<schemars::NumberValidation>>,
-    format: Option<&str>,
-    enums: &mut [serde_json::Value],
-) -> openapi::IntegerType {
-    fn convert_f64(input: f64) -> i64 {
+    /// Convert an integer schema's constraint `keyword`
+    fn convert_f64(&mut self, keyword: &'static str, input: f64) -> i64 {
         if input.fract() != 0.0 {
-            warn!("Integer type has decimal constraints");
+            self.report(ConversionDiagnosticKind::FractionalIntegerConstraint(
+                keyword,
+            ));
         }
         input as i64
     }
 
-    let openapi::NumberType {
-        format: _,
-        multiple_of,
-        exclusive_minimum,
-        exclusive_maximum,
-        minimum,
-        maximum,
-        enumeration: _,
-    } = convert_number_type(input, None, &mut []);
+    fn convert_u32(&mut self, input: u32) -> usize {
+        match input.try_into() {
+            Ok(output) => output,
+            Err(_) => {
+                self.report(ConversionDiagnosticKind::LengthOverflow);
+                usize::MAX
+            }
+        }
+    }
+}
 
-    let mut enumeration = Vec::new();
-    for value in enums {
-        match value {
-            serde_json::Value::Null => enumeration.push(None),
-            serde_json::Value::Number(number) => {
-                if let Some(number) = number.as_i64() {
-                    enumeration.push(Some(number));
-                }
+/// Get the instance type of a `const` value
+fn instance_type_of(value: &serde_json::Value) -> schemars::InstanceType {
+    match value {
+        serde_json::Value::Null => schemars::InstanceType::Null,
+        serde_json::Value::Bool(_) => schemars::InstanceType::Boolean,
+        serde_json::Value::Number(number) if number.is_f64() => schemars::InstanceType::Number,
+        serde_json::Value::Number(_) => schemars::InstanceType::Integer,
+        serde_json::Value::String(_) => schemars::InstanceType::String,
+        serde_json::Value::Array(_) => schemars::InstanceType::Array,
+        serde_json::Value::Object(_) => schemars::InstanceType::Object,
+    }
+}
+
+/// Find a property all `one_of`'s members can be distinguished by
+///
+/// This is the tag of serde's internally tagged enums
+/// which schemars emits as a required string property with a single allowed value.
+///
+/// Members which are (or extend) a reference are mapped to it using their tag's value.
+fn find_discriminator(
+    one_of: &[openapi::ReferenceOr<openapi::Schema>],
+) -> Option<openapi::Discriminator> {
+    let members = one_of
+        .iter()
+        .map(|member| match member {
+            openapi::ReferenceOr::Item(schema) => Some(schema),
+            openapi::ReferenceOr::Reference { .. } => None,
+        })
+        .collect::<Option<Vec<_>>>()?;
+    let (first, others) = members.split_first()?;
+
+    let property_name = tags(first).into_iter().map(|(name, _)| name).find(|name| {
+        let mut values = vec![tag_value(first, name)];
+        others.iter().all(|member| {
+            let value = tag_value(member, name);
+            let unique = value.is_some() && !values.contains(&value);
+            values.push(value);
+            unique
+        })
+    })?;
+
+    let mut mapping = IndexMap::new();
+    for member in &members {
+        let openapi::SchemaKind::AllOf { all_of } = &member.schema_kind else {
+            continue;
+        };
+        let mut references = all_of.iter().filter_map(|part| match part {
+            openapi::ReferenceOr::Reference { reference } => Some(reference),
+            openapi::ReferenceOr::Item(_) => None,
+        });
+        if let (Some(reference), None) = (references.next(), references.next()) {
+            if let Some(value) = tag_value(member, property_name) {
+                mapping.insert(value.to_string(), reference.clone());
             }
-            _ => {}
         }
     }
 
-    openapi::IntegerType {
-        format: convert_format(format),
-        multiple_of: multiple_of.map(convert_f64),
-        exclusive_minimum,
-        exclusive_maximum,
-        minimum: minimum.map(convert_f64),
-        maximum: maximum.map(convert_f64),
-        enumeration,
+    Some(openapi::Discriminator {
+        property_name: property_name.to_string(),
+        mapping,
+        extensions: Default::default(),
+    })
+}
+
+/// Get the value of the tag property `name` (see [`tags`])
+fn tag_value<'a>(schema: &'a openapi::Schema, name: &str) -> Option<&'a str> {
+    tags(schema)
+        .into_iter()
+        .find_map(|(tag, value)| (tag == name).then_some(value))
+}
+
+/// Get the required string properties of an object schema which only allow a single value
+///
+/// The object may be wrapped in an `allOf`.
+fn tags(schema: &openapi::Schema) -> Vec<(&str, &str)> {
+    match &schema.schema_kind {
+        openapi::SchemaKind::Type(openapi::Type::Object(object)) => object
+            .properties
+            .iter()
+            .filter(|(name, _)| object.required.contains(name))
+            .filter_map(|(name, property)| match property {
+                openapi::ReferenceOr::Item(property) => match &property.schema_kind {
+                    openapi::SchemaKind::Type(openapi::Type::String(string)) => {
+                        match string.enumeration.as_slice() {
+                            [Some(value)] => Some((name.as_str(), value.as_str())),
+                            _ => None,
+                        }
+                    }
+                    _ => None,
+                },
+                openapi::ReferenceOr::Reference { .. } => None,
+            })
+            .collect(),
+        openapi::SchemaKind::AllOf { all_of } => all_of
+            .iter()
+            .filter_map(|part| match part {
+                openapi::ReferenceOr::Item(part) => Some(tags(part)),
+                openapi::ReferenceOr::Reference { .. } => None,
+            })
+            .flatten()
+            .collect(),
+        _ => Vec::new(),
     }
 }
 
+#[derive(Default)]
+struct InstanceTypes {
+    is_null: bool,
+    is_boolean: bool,
+    is_object: bool,
+    is_array: bool,
+    is_number: bool,
+    is_string: bool,
+    is_integer: bool,
+}
+
 fn convert_boolean_type(enums: &mut [serde_json::Value]) -> openapi::BooleanType {
     let mut enumeration = Vec::new();
     for value in enums {
@@ -528,16 +756,6 @@ fn convert_format<T: DeserializeOwned>(input: Option<&str>) -> openapi::VariantO
     }
 }
 
-fn convert_u32(input: u32) -> usize {
-    match input.try_into() {
-        Ok(output) => output,
-        Err(_) => {
-            warn!("Couldn't convert u32 lossless to usize");
-            usize::MAX
-        }
-    }
-}
-
 fn box_reference_or<T>(input: openapi::ReferenceOr<T>) -> openapi::ReferenceOr<Box<T>> {
     match input {
         openapi::ReferenceOr::Item(item) => openapi::ReferenceOr::Item(Box::new(item)),
@@ -546,3 +764,139 @@ fn box_reference_or<T>(input: openapi::ReferenceOr<T>) -> openapi::ReferenceOr<B
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+    use serde_json::Value;
+
+    use super::*;
+
+    /// Convert a json schema returning the result as json and the diagnostics
+    fn convert(schema: Value) -> (Value, Vec<ConversionDiagnostic>) {
+        let schema = serde_json::from_value(schema).unwrap();
+        let mut diagnostics = Vec::new();
+        let schema = convert_schema_with_diagnostics(schema, "Test", &mut diagnostics);
+        (serde_json::to_value(schema).unwrap(), diagnostics)
+    }
+
+    fn diagnostic(pointer: &str, kind: ConversionDiagnosticKind) -> ConversionDiagnostic {
+        ConversionDiagnostic {
+            schema: "Test".to_string(),
+            pointer: pointer.to_string(),
+            kind,
+        }
+    }
+
+    #[test]
+    fn const_becomes_enum() {
+        let (schema, diagnostics) = convert(json!({"const": "a"}));
+        assert_eq!(schema, json!({"type": "string", "enum": ["a"]}));
+        assert_eq!(diagnostics, []);
+
+        let (schema, diagnostics) = convert(json!({"type": "integer", "const": 1}));
+        assert_eq!(schema, json!({"type": "integer", "enum": [1]}));
+        assert_eq!(diagnostics, []);
+    }
+
+    #[test]
+    fn const_contradicting_enum_is_reported() {
+        let (schema, diagnostics) =
+            convert(json!({"enum": ["a", "b"], "const": "c", "type": "string"}));
+        assert_eq!(schema, json!({"type": "string", "enum": ["a", "b"]}));
+        assert_eq!(
+            diagnostics,
+            [diagnostic(
+                "",
+                ConversionDiagnosticKind::UnsupportedKeyword("const")
+            )]
+        );
+    }
+
+    #[test]
+    fn discriminator_is_detected() {
+        let variant = |tag: &str, field: &str| {
+            json!({
+                "type": "object",
+                "required": ["type", field],
+                "properties": {
+                    "type": {"type": "string", "enum": [tag]},
+                    field: {"type": "string"}
+                }
+            })
+        };
+        let (schema, diagnostics) = convert(json!({
+            "oneOf": [
+                variant("Cat", "meow"),
+                {
+                    "allOf": [
+                        {"$ref": "#/components/schemas/Dog"},
+                        {"type": "object", "required": ["type"], "properties": {"type": {"type": "string", "enum": ["Dog"]}}}
+                    ]
+                },
+                variant("Bird", "tweet")
+            ]
+        }));
+        assert_eq!(
+            schema["discriminator"],
+            json!({"propertyName": "type", "mapping": {"Dog": "#/components/schemas/Dog"}})
+        );
+        assert_eq!(diagnostics, []);
+    }
+
+    #[test]
+    fn discriminator_requires_unique_tags() {
+        let variant = |tag: &str| {
+            json!({
+                "type": "object",
+                "required": ["type"],
+                "properties": {"type": {"type": "string", "const": tag}}
+            })
+        };
+        let (schema, _) = convert(json!({"oneOf": [variant("A"), variant("B")]}));
+        assert_eq!(schema["discriminator"]["propertyName"], "type");
+
+        let (schema, _) = convert(json!({"oneOf": [variant("A"), variant("A")]}));
+        assert_eq!(schema.get("discriminator"), None);
+
+        let (schema, _) = convert(json!({"oneOf": [variant("A"), {"type": "string"}]}));
+        assert_eq!(schema.get("discriminator"), None);
+    }
+
+    #[test]
+    fn diagnostics_point_to_the_sub_schema() {
+        let (_, diagnostics) = convert(json!({
+            "type": "object",
+            "properties": {
+                "a/b~c": {
+                    "anyOf": [
+                        {"type": "string"},
+                        {"type": "object", "patternProperties": {"^x-": true}}
+                    ]
+                },
+                "list": {
+                    "type": "array",
+                    "items": {"type": "integer", "minimum": 1.5}
+                }
+            },
+            "additionalProperties": {"if": {"type": "string"}}
+        }));
+        assert_eq!(
+            diagnostics,
+            [
+                diagnostic(
+                    "/properties/a~1b~0c/anyOf/1",
+                    ConversionDiagnosticKind::UnsupportedKeyword("patternProperties")
+                ),
+                diagnostic(
+                    "/properties/list/items",
+                    ConversionDiagnosticKind::FractionalIntegerConstraint("minimum")
+                ),
+                diagnostic(
+                    "/additionalProperties",
+                    ConversionDiagnosticKind::UnsupportedKeyword("if")
+                ),
+            ]
+        );
+    }
+}
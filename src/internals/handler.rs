@@ -5,8 +5,10 @@ use openapiv3::Responses;
 use crate::handler_argument::HandlerArgumentFns;
 use crate::internals::ptrset::PtrSet;
 use crate::internals::HttpMethod;
+use crate::internals::PageHandle;
 use crate::internals::SchemaGenerator;
-use crate::SwaggapiPageBuilder;
+use crate::security::SecurityRequirement;
+use crate::tags::Tag;
 
 /// Meta information about a handler gathered by the [`#[handler]`](crate::handler) macro
 #[derive(Copy, Clone, Debug)]
@@ -26,9 +28,18 @@ pub struct SwaggapiHandler {
     /// The handler's identifier
     pub ident: &'static str,
 
+    /// The [`module_path!`] of the module the handler is declared in
+    pub module_path: &'static str,
+
+    /// The operation id set through `#[operation(..., operation_id = "...")]`
+    pub operation_id: Option<&'static str>,
+
     /// Tags set through `#[operation(..., tags(...))]`
     pub tags: &'static [&'static str],
 
+    /// Security requirements set through `#[operation(..., security(...))]`
+    pub security: &'static [SecurityRequirement],
+
     /// The handler's return type's [`AsResponses::responses`](crate::as_responses::AsResponses::responses)
     pub responses: fn(&mut SchemaGenerator) -> Responses,
 
@@ -43,8 +54,12 @@ pub struct SwaggapiHandler {
     pub actix: (),
 
     /// The actual function stored in an axum specific format
+    ///
+    /// The returned value is a [`MethodRouter<S>`](::axum::routing::MethodRouter)
+    /// where `S` is the state required by the handler (or `()` if it doesn't require any).
+    /// Since this type would differ between handlers, it is stored as [`Any`](std::any::Any).
     #[cfg(feature = "axum")]
-    pub axum: fn() -> ::axum::routing::MethodRouter,
+    pub axum: fn() -> Box<dyn std::any::Any + Send>,
     /// Placeholder to make the macro code cleaner
     #[cfg(not(feature = "axum"))]
     pub axum: (),
@@ -75,7 +90,7 @@ macro_rules! impl_Foo_actix {
 #[macro_export]
 #[doc(hidden)]
 macro_rules! impl_Foo_axum {
-    ($method:expr, $ident:ident) => {
+    ($method:expr, $ident:ident, $state:ty) => {
         ()
     };
 }
@@ -83,8 +98,13 @@ macro_rules! impl_Foo_axum {
 #[macro_export]
 #[doc(hidden)]
 macro_rules! impl_Foo_axum {
-    ($method:expr, $ident:ident) => {
-        || $crate::re_exports::axum::routing::MethodRouter::new().on($method.axum(), $ident)
+    ($method:expr, $ident:ident, $state:ty) => {
+        || {
+            ::std::boxed::Box::new(
+                $crate::re_exports::axum::routing::MethodRouter::<$state>::new()
+                    .on($method.axum(), $ident),
+            )
+        }
     };
 }
 
@@ -101,8 +121,18 @@ pub struct ContextHandler {
     /// The handler's modified path
     pub tags: PtrSet<'static, str>,
 
+    /// The definitions of some of the handler's tags
+    ///
+    /// They are added to every page the handler is added to.
+    pub tag_definitions: Vec<Tag>,
+
+    /// The handler's modified security requirements
+    ///
+    /// An empty list means the page's default requirements apply.
+    pub security: Vec<SecurityRequirement>,
+
     /// The pages the handler should be added to
-    pub pages: PtrSet<'static, SwaggapiPageBuilder>,
+    pub pages: Vec<PageHandle>,
 }
 impl ContextHandler {
     /// Constructs a new `ContextHandler`
@@ -111,7 +141,9 @@ impl ContextHandler {
             original,
             path: original.path.to_string(),
             tags: PtrSet::from_iter(original.tags.iter().copied()),
-            pages: PtrSet::new(),
+            tag_definitions: Vec::new(),
+            security: original.security.to_vec(),
+            pages: Vec::new(),
         }
     }
 }
@@ -3,13 +3,18 @@
 mod convert_schema;
 mod handler;
 mod http_method;
+mod openapi_3_1;
+pub(crate) mod ordered_json;
 mod page_builder;
 mod ptrset;
 mod schema_generator;
+pub(crate) mod walk;
 
 pub use self::convert_schema::convert_schema;
+pub use self::convert_schema::convert_schema_with_diagnostics;
 pub use self::handler::{ContextHandler, SwaggapiHandler};
 pub use self::http_method::HttpMethod;
 pub use self::page_builder::AccessSwaggapiPageBuilder;
+pub use self::page_builder::PageHandle;
 pub use self::page_builder::SwaggapiPageBuilderImpl;
 pub use self::schema_generator::SchemaGenerator;
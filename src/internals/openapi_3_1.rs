@@ -0,0 +1,137 @@
+use serde_json::json;
+use serde_json::Map;
+use serde_json::Value;
+
+use crate::internals::walk::walk_document_mut;
+use crate::internals::walk::Position;
+
+/// Rewrites the OpenAPI 3.0 specific schema keywords in a document to their OpenAPI 3.1 equivalent
+///
+/// - `nullable: true` is replaced by adding `"null"` to the schema's `type`
+///   (or wrapping the schema in an `anyOf` if it has no `type`)
+/// - boolean `exclusiveMinimum` and `exclusiveMaximum` are replaced by numeric ones
+///
+/// Only schemas are rewritten, payloads like examples or default values are left untouched.
+pub(crate) fn upgrade_to_3_1(document: &mut Value) {
+    walk_document_mut(document, &mut |position, _, value| {
+        if let (Position::Schema, Value::Object(schema)) = (position, value) {
+            upgrade_exclusive_bound(schema, "minimum", "exclusiveMinimum");
+            upgrade_exclusive_bound(schema, "maximum", "exclusiveMaximum");
+            upgrade_nullable(schema);
+        }
+    });
+}
+
+fn upgrade_exclusive_bound(object: &mut Map<String, Value>, bound: &str, exclusive: &str) {
+    match object.get(exclusive) {
+        Some(Value::Bool(true)) => match object.remove(bound) {
+            Some(bound) => {
+                object.insert(exclusive.to_string(), bound);
+            }
+            None => {
+                object.remove(exclusive);
+            }
+        },
+        Some(Value::Bool(false)) => {
+            object.remove(exclusive);
+        }
+        _ => {}
+    }
+}
+
+fn upgrade_nullable(object: &mut Map<String, Value>) {
+    let Some(Value::Bool(nullable)) = object.get("nullable") else {
+        return;
+    };
+    let nullable = *nullable;
+    object.remove("nullable");
+    if !nullable {
+        return;
+    }
+
+    if let Some(Value::Array(values)) = object.get_mut("enum") {
+        if !values.contains(&Value::Null) {
+            values.push(Value::Null);
+        }
+    }
+    match object.get_mut("type") {
+        Some(Value::String(single)) => {
+            let single = single.clone();
+            object.insert("type".to_string(), json!([single, "null"]));
+        }
+        Some(Value::Array(types)) => {
+            if !types.contains(&json!("null")) {
+                types.push(json!("null"));
+            }
+        }
+        _ => {
+            let schema = std::mem::take(object);
+            object.insert(
+                "anyOf".to_string(),
+                json!([Value::Object(schema), {"type": "null"}]),
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::upgrade_to_3_1;
+
+    #[test]
+    fn schemas_are_upgraded() {
+        let mut document = json!({
+            "paths": {"/": {"get": {
+                "parameters": [{"name": "limit", "in": "query", "schema": {
+                    "type": "integer", "minimum": 0, "exclusiveMinimum": true
+                }}],
+                "responses": {"200": {"content": {"application/json": {"schema": {
+                    "type": "object",
+                    "properties": {"name": {"type": "string", "nullable": true}}
+                }}}}}
+            }}},
+            "components": {"schemas": {"Nested": {
+                "allOf": [{"$ref": "#/components/schemas/Other"}],
+                "nullable": true
+            }}}
+        });
+        upgrade_to_3_1(&mut document);
+
+        let operation = &document["paths"]["/"]["get"];
+        assert_eq!(
+            operation["parameters"][0]["schema"],
+            json!({"type": "integer", "exclusiveMinimum": 0})
+        );
+        assert_eq!(
+            operation["responses"]["200"]["content"]["application/json"]["schema"]["properties"]
+                ["name"],
+            json!({"type": ["string", "null"]})
+        );
+        assert_eq!(
+            document["components"]["schemas"]["Nested"],
+            json!({"anyOf": [{"allOf": [{"$ref": "#/components/schemas/Other"}]}, {"type": "null"}]})
+        );
+    }
+
+    #[test]
+    fn payloads_are_untouched() {
+        let payload = json!({"nullable": true, "minimum": 1, "exclusiveMinimum": true});
+        let mut document = json!({
+            "paths": {"/": {"post": {
+                "requestBody": {"content": {"application/json": {
+                    "schema": {"type": "object", "default": payload, "example": payload},
+                    "example": payload,
+                    "examples": {"first": {"value": payload}}
+                }}},
+                "responses": {},
+                "x-payload": payload
+            }}},
+            "components": {"schemas": {"Enum": {"enum": [payload]}}}
+        });
+        let expected = document.clone();
+        upgrade_to_3_1(&mut document);
+        assert_eq!(document, expected);
+    }
+}
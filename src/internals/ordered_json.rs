@@ -0,0 +1,107 @@
+use indexmap::IndexMap;
+use serde::Deserialize;
+use serde::Serialize;
+use serde_json::Number;
+use serde_json::Value;
+
+/// A JSON value whose objects keep the order of their keys
+///
+/// A [`Value`] sorts its objects' keys (unless serde_json's `preserve_order` feature is enabled),
+/// which would list a document's paths alphabetically instead of in the order they were added.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub(crate) enum OrderedJson {
+    Null,
+    Bool(bool),
+    Number(Number),
+    String(String),
+    Array(Vec<OrderedJson>),
+    Object(IndexMap<String, OrderedJson>),
+}
+
+impl OrderedJson {
+    /// Serializes a value keeping the order of its maps and struct fields
+    pub(crate) fn new(value: &impl Serialize) -> Self {
+        let json = serde_json::to_string(value).expect("Value should serialize to json");
+        serde_json::from_str(&json).expect("Serialized json should be valid")
+    }
+
+    /// Converts a [`Value`] ordering the keys of its objects like the ones of `order`
+    ///
+    /// Keys which are missing in `order` follow the others.
+    pub(crate) fn reordered(value: &Value, order: &Self) -> Self {
+        match value {
+            Value::Null => Self::Null,
+            Value::Bool(bool) => Self::Bool(*bool),
+            Value::Number(number) => Self::Number(number.clone()),
+            Value::String(string) => Self::String(string.clone()),
+            Value::Array(array) => Self::Array(
+                array
+                    .iter()
+                    .enumerate()
+                    .map(|(index, value)| {
+                        let order = match order {
+                            Self::Array(order) => order.get(index),
+                            _ => None,
+                        };
+                        Self::reordered(value, order.unwrap_or(&Self::Null))
+                    })
+                    .collect(),
+            ),
+            Value::Object(object) => {
+                let order = match order {
+                    Self::Object(order) => Some(order),
+                    _ => None,
+                };
+                let known = order
+                    .into_iter()
+                    .flat_map(IndexMap::keys)
+                    .filter(|key| object.contains_key(*key));
+                let unknown = object
+                    .keys()
+                    .filter(|key| !order.is_some_and(|order| order.contains_key(*key)));
+                Self::Object(
+                    known
+                        .chain(unknown)
+                        .map(|key| {
+                            let order = order.and_then(|order| order.get(key));
+                            let value = Self::reordered(&object[key], order.unwrap_or(&Self::Null));
+                            (key.clone(), value)
+                        })
+                        .collect(),
+                )
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn keeps_the_order_of_maps() {
+        let map = IndexMap::from([("b", 1), ("a", 2)]);
+        assert_eq!(
+            serde_json::to_string(&OrderedJson::new(&map)).unwrap(),
+            r#"{"b":1,"a":2}"#
+        );
+    }
+
+    #[test]
+    fn reorders_like_the_template() {
+        let order: OrderedJson =
+            serde_json::from_str(r#"{"paths":{"/b":null,"/a":null},"info":null}"#).unwrap();
+        let value = json!({
+            "info": {"title": "x"},
+            "paths": {"/a": {}, "/b": {}},
+            "webhooks": {"x": {}, "y": {}},
+        });
+        assert_eq!(
+            serde_json::to_string(&OrderedJson::reordered(&value, &order)).unwrap(),
+            r#"{"paths":{"/b":{},"/a":{}},"info":{"title":"x"},"webhooks":{"x":{},"y":{}}}"#
+        );
+    }
+}
@@ -1,4 +1,10 @@
+use std::collections::hash_map::Entry;
 use std::collections::BTreeMap;
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::mem;
+use std::ops::Deref;
+use std::ptr;
 use std::sync::{Arc, OnceLock};
 
 use indexmap::IndexMap;
@@ -11,19 +17,37 @@ use openapiv3::Operation;
 use openapiv3::PathItem;
 use openapiv3::Paths;
 use openapiv3::ReferenceOr;
+use openapiv3::Responses;
 use regex::Regex;
 use schemars::schema::Schema;
 use schemars::JsonSchema;
+use serde_json::Value;
 
+use crate::as_responses::merge_responses;
+use crate::diagnostics::ConflictingRequestBodies;
+use crate::diagnostics::ConversionDiagnostic;
+use crate::diagnostics::DuplicateOperationId;
+use crate::diagnostics::DuplicateRoute;
+use crate::internals::openapi_3_1::upgrade_to_3_1;
+use crate::internals::ordered_json::OrderedJson;
 use crate::internals::HttpMethod;
 use crate::internals::SchemaGenerator;
-use crate::internals::{convert_schema, ContextHandler};
+use crate::internals::{convert_schema_with_diagnostics, ContextHandler};
+use crate::page::DuplicateRouteBehavior;
+use crate::page::OpenApiVersion;
+use crate::page::OperationIdStrategy;
 use crate::page::SwaggapiPageBuilder;
+use crate::security::SecurityRequirement;
+use crate::security::SecurityScheme;
+use crate::servers::Server;
+use crate::tags::Tag;
+use crate::tags::TagGroup;
 
-/// This trait associates one static instance of a [`SwaggapiPageBuilder`] to its implementor.
+/// This trait associates one instance of a [`SwaggapiPageBuilder`] to its implementor.
 ///
 /// It is the implementation detail of [`SwaggapiPage`](trait@crate::SwaggapiPage)
-/// and should be implemented through [`#[derive(SwaggapiPage)]`](macro@crate::SwaggapiPage).
+/// and should be implemented through [`#[derive(SwaggapiPage)]`](macro@crate::SwaggapiPage)
+/// or by using a [`RuntimePage`](crate::RuntimePage).
 pub trait AccessSwaggapiPageBuilder {
     /// Get the [`SwaggapiPageBuilder`]
     ///
@@ -31,19 +55,59 @@ pub trait AccessSwaggapiPageBuilder {
     /// ```rust
     /// # use swaggapi::SwaggapiPageBuilder;
     /// # use swaggapi::internals::AccessSwaggapiPageBuilder;
+    /// # use swaggapi::internals::PageHandle;
     /// # struct Test;
     /// # impl AccessSwaggapiPageBuilder for Test {
-    /// fn get_builder(&self) -> &'static SwaggapiPageBuilder {
+    /// fn get_builder(&self) -> PageHandle {
     ///     static BUILDER: SwaggapiPageBuilder = SwaggapiPageBuilder::new();
-    ///     &BUILDER
+    ///     PageHandle::Static(&BUILDER)
     /// }
     /// # }
     /// ```
-    fn get_builder(&self) -> &'static SwaggapiPageBuilder;
+    ///
+    /// # Breaking change
+    /// This method used to return `&'static SwaggapiPageBuilder`.
+    /// Manual implementations have to wrap their `static` builder in [`PageHandle::Static`] like above.
+    /// Callers receive a [`PageHandle`] instead which dereferences to the builder.
+    fn get_builder(&self) -> PageHandle;
 }
 impl AccessSwaggapiPageBuilder for &'static SwaggapiPageBuilder {
-    fn get_builder(&self) -> &'static SwaggapiPageBuilder {
-        *self
+    fn get_builder(&self) -> PageHandle {
+        PageHandle::Static(self)
+    }
+}
+
+/// Owned reference to a [`SwaggapiPageBuilder`]
+///
+/// It is used to store the pages an [`ApiContext`](crate::ApiContext) or [`SwaggerUi`](crate::SwaggerUi) uses.
+/// Two handles are equal if they refer to the same builder.
+#[derive(Clone, Debug)]
+pub enum PageHandle {
+    /// A builder stored in a `static`
+    Static(&'static SwaggapiPageBuilder),
+
+    /// A builder created at runtime (see [`RuntimePage`](crate::RuntimePage))
+    Shared(Arc<SwaggapiPageBuilder>),
+}
+impl Deref for PageHandle {
+    type Target = SwaggapiPageBuilder;
+
+    fn deref(&self) -> &Self::Target {
+        match self {
+            PageHandle::Static(builder) => builder,
+            PageHandle::Shared(builder) => builder,
+        }
+    }
+}
+impl AccessSwaggapiPageBuilder for PageHandle {
+    fn get_builder(&self) -> PageHandle {
+        self.clone()
+    }
+}
+impl Eq for PageHandle {}
+impl PartialEq for PageHandle {
+    fn eq(&self, other: &Self) -> bool {
+        ptr::eq::<SwaggapiPageBuilder>(&**self, &**other)
     }
 }
 
@@ -52,14 +116,46 @@ impl AccessSwaggapiPageBuilder for &'static SwaggapiPageBuilder {
 /// This struct implements the actual construction of an [`OpenAPI`] document
 /// combining the handlers added trough [`SwaggapiPageBuilderImpl::add_handler`]
 /// and the metadata stored in [`SwaggapiPageBuilder`].
-#[derive(Default)]
+#[derive(Clone, Default)]
 pub struct SwaggapiPageBuilderImpl {
     paths: Paths,
 
     schemas: BTreeMap<String, Schema>,
 
-    /// Cache for the result of [`SwaggapiPageBuilder::build`]
+    /// Problems converting the inlined schemas of the added handlers
+    ///
+    /// The ones of `schemas` are collected while building.
+    diagnostics: Vec<ConversionDiagnostic>,
+
+    /// The path and handler (as `module::path::ident`) documented for each method and [`normalize_path`]
+    routes: HashMap<(String, HttpMethod), (String, String)>,
+
+    /// Handlers which were added for an already documented path and method
+    duplicate_routes: Vec<DuplicateRoute>,
+
+    /// Handlers with several arguments consuming the request body
+    conflicting_request_bodies: Vec<ConflictingRequestBodies>,
+
+    /// The explicit `operationId` of each operation (as `METHOD /path` or `METHOD webhook`) setting one
+    explicit_operation_ids: HashMap<String, String>,
+
+    /// Security schemes added implicitly through [`HandlerArgument::security_schemes`](crate::handler_argument::HandlerArgument::security_schemes)
+    security_schemes: BTreeMap<&'static str, SecurityScheme>,
+
+    /// Tags defined by the added handlers' [`ApiContext`](crate::ApiContext)s
+    tags: Vec<Tag>,
+
+    /// Webhooks added through [`SwaggapiPageBuilderImpl::add_webhook`]
+    webhooks: IndexMap<String, PathItem>,
+
+    /// Counter incremented by every change invalidating the cached builds
+    generation: u64,
+
+    /// Cache for the result of [`SwaggapiPageBuilderImpl::build`]
     last_build: Option<Arc<OpenAPI>>,
+
+    /// Cache for the results of [`SwaggapiPageBuilderImpl::build_json`] and [`SwaggapiPageBuilderImpl::build_ordered_json`]
+    last_json_build: Option<(Arc<Value>, Arc<OrderedJson>)>,
 }
 
 impl SwaggapiPageBuilderImpl {
@@ -69,40 +165,180 @@ impl SwaggapiPageBuilderImpl {
     pub fn add_schema<T: JsonSchema>(builder: &SwaggapiPageBuilder) {
         let mut guard = builder.state.lock().unwrap();
         let state = guard.get_or_insert_with(Default::default);
-        state.last_build = None;
+        state.invalidate();
 
-        SchemaGenerator::employ(&mut state.schemas, |gen| gen.generate::<T>());
+        SchemaGenerator::employ(&mut state.schemas, &mut state.diagnostics, |gen| {
+            gen.generate::<T>()
+        });
     }
 
     /// Add a handler to this api page
     pub fn add_handler(builder: &SwaggapiPageBuilder, handler: &ContextHandler) {
         let mut guard = builder.state.lock().unwrap();
         let state = guard.get_or_insert_with(Default::default);
-        state.last_build = None;
+        state.invalidate();
+
+        let name = format!("{}::{}", handler.module_path, handler.ident);
+        match state
+            .routes
+            .entry((normalize_path(&handler.path), handler.method))
+        {
+            Entry::Vacant(entry) => {
+                entry.insert((handler.path.clone(), name.clone()));
+            }
+            // The same handler added again (e.g. through several pages) only replaces itself
+            Entry::Occupied(entry) if entry.get().1 == name => {}
+            Entry::Occupied(mut entry) => {
+                let (first_path, first) = entry.get().clone();
+                state.duplicate_routes.push(DuplicateRoute {
+                    method: handler.method,
+                    path: first_path.clone(),
+                    second_path: handler.path.clone(),
+                    first,
+                    second: name.clone(),
+                });
+                if builder.on_duplicate_route == DuplicateRouteBehavior::FirstWins {
+                    return;
+                }
+                entry.insert((handler.path.clone(), name.clone()));
+
+                // The path parameters are named differently, so the replaced operation is stored under another path
+                if first_path != handler.path {
+                    if let Some(ReferenceOr::Item(item)) = state.paths.paths.get_mut(&first_path) {
+                        *operation_mut(item, handler.method) = None;
+                        if METHODS
+                            .into_iter()
+                            .all(|method| operation_mut(item, method).is_none())
+                        {
+                            state.paths.paths.shift_remove(&first_path);
+                        }
+                    }
+                }
+            }
+        }
+        state.claim_operation_id(handler, format!("{} {}", handler.method, handler.path));
+
+        let operation = state.operation(builder, handler);
+        for tag in &handler.tag_definitions {
+            if !state.tags.iter().any(|known| known.name == tag.name) {
+                state.tags.push(*tag);
+            }
+        }
+        let ReferenceOr::Item(path) = state
+            .paths
+            .paths
+            .entry(handler.path.to_string())
+            .or_insert_with(|| ReferenceOr::Item(PathItem::default()))
+        else {
+            unreachable!("We only ever insert ReferenceOr::Item. See above")
+        };
+        *operation_mut(path, handler.method) = Some(operation);
+    }
 
+    /// Add a webhook to this api page
+    ///
+    /// The handler describes the request the api sends to its subscribers
+    /// and the responses it expects, its path is ignored.
+    ///
+    /// Webhooks are only included in OpenAPI 3.1 documents
+    /// (see [`SwaggapiPageBuilder::openapi_version`]).
+    pub fn add_webhook(builder: &SwaggapiPageBuilder, name: &str, handler: &ContextHandler) {
+        let mut guard = builder.state.lock().unwrap();
+        let state = guard.get_or_insert_with(Default::default);
+        state.invalidate();
+
+        state.claim_operation_id(handler, format!("{} {name}", handler.method));
+
+        let operation = state.operation(builder, handler);
+        let path = state.webhooks.entry(name.to_string()).or_default();
+        *operation_mut(path, handler.method) = Some(operation);
+    }
+
+    /// Remembers the explicit `operationId` of the handler documenting `location`
+    fn claim_operation_id(&mut self, handler: &ContextHandler, location: String) {
+        // The handler replaces the one previously documenting `location`
+        let normalized = normalize_path(&location);
+        self.explicit_operation_ids
+            .retain(|other, _| normalize_path(other) != normalized);
+        if let Some(operation_id) = handler.operation_id {
+            self.explicit_operation_ids
+                .insert(location, operation_id.to_string());
+        }
+    }
+
+    /// Clears the cached builds
+    fn invalidate(&mut self) {
+        self.generation += 1;
+        self.last_build = None;
+        self.last_json_build = None;
+    }
+
+    /// Construct the [`Operation`] describing a handler
+    ///
+    /// Problems with the handler are stored in `conflicting_request_bodies`.
+    fn operation(&mut self, builder: &SwaggapiPageBuilder, handler: &ContextHandler) -> Operation {
+        let mut security = handler
+            .security
+            .iter()
+            .map(SecurityRequirement::to_openapi)
+            .collect::<Vec<_>>();
         let (parameters, mut request_body, responses) =
-            SchemaGenerator::employ(&mut state.schemas, |gen| {
+            SchemaGenerator::employ(&mut self.schemas, &mut self.diagnostics, |gen| {
                 let mut parameters = Vec::new();
                 let mut request_body = Vec::new();
-                for arg in handler.handler_arguments {
+                let mut argument_responses = Responses::default();
+                let mut argument_schemes = Vec::new();
+                for (position, arg) in handler.handler_arguments.iter().enumerate() {
                     if let Some(arg) = arg.as_ref() {
-                        static PATH_PARAM_REGEX: OnceLock<Regex> = OnceLock::new();
-                        let path_param_regex =
-                            PATH_PARAM_REGEX.get_or_init(|| Regex::new(r"\{[^}]*}").unwrap());
-                        let path_params = path_param_regex
+                        let path_params = path_param_regex()
                             .find_iter(&handler.path)
                             .map(|needle| &handler.path[(needle.start() + 1)..(needle.end() - 1)])
                             .collect::<Vec<_>>();
 
-                        parameters.extend(
-                            (arg.parameters)(&mut *gen, &path_params)
-                                .into_iter()
-                                .map(ReferenceOr::Item),
-                        );
-                        request_body.extend((arg.request_body)(&mut *gen));
+                        let bodyless = matches!(handler.method, HttpMethod::Get | HttpMethod::Head);
+                        let query_only = (arg.query_for_get_and_head)();
+                        if !query_only || bodyless {
+                            parameters.extend(
+                                (arg.parameters)(&mut *gen, &path_params)
+                                    .into_iter()
+                                    .map(ReferenceOr::Item),
+                            );
+                        }
+                        if !query_only || !bodyless {
+                            request_body.extend(
+                                (arg.request_body)(&mut *gen).map(|body| (position + 1, body)),
+                            );
+                        }
+                        merge_responses(&mut argument_responses, (arg.responses)(&mut *gen));
+                        if builder.rejection_responses {
+                            merge_responses(
+                                &mut argument_responses,
+                                (arg.rejection_responses)(&mut *gen),
+                            );
+                        }
+
+                        for (name, scheme) in (arg.security_schemes)() {
+                            self.security_schemes.insert(name, scheme);
+                            argument_schemes.push(name);
+                        }
+                    }
+                }
+                // Every extractor performs its authentication,
+                // so their schemes are required in addition to any alternative the handler declares
+                if !argument_schemes.is_empty() {
+                    if security.is_empty() {
+                        security.push(IndexMap::new());
+                    }
+                    for requirement in &mut security {
+                        for name in &argument_schemes {
+                            requirement.entry(name.to_string()).or_default();
+                        }
                     }
                 }
-                let responses = (handler.responses)(&mut *gen);
+                let mut responses = (handler.responses)(&mut *gen);
+                for (status_code, response) in argument_responses.responses {
+                    responses.responses.entry(status_code).or_insert(response);
+                }
                 (parameters, request_body, responses)
             });
 
@@ -116,42 +352,32 @@ impl SwaggapiPageBuilderImpl {
                 .fold(summary, |text, line| format!("{text}\n{}", line.trim()))
         });
 
-        let operation = Operation {
+        if request_body.len() > 1 {
+            let conflict = ConflictingRequestBodies {
+                handler: format!("{}::{}", handler.module_path, handler.ident),
+                arguments: request_body.iter().map(|(position, _)| *position).collect(),
+            };
+            if !self.conflicting_request_bodies.contains(&conflict) {
+                self.conflicting_request_bodies.push(conflict);
+            }
+        }
+
+        Operation {
             summary,
             description,
-            operation_id: Some(handler.ident.to_string()),
+            operation_id: Some(operation_id(builder.operation_ids, handler)),
             parameters,
-            request_body: request_body.pop().map(ReferenceOr::Item),
+            request_body: request_body.pop().map(|(_, body)| ReferenceOr::Item(body)),
             responses,
             deprecated: handler.deprecated,
-            security: None, // TODO
+            security: (!security.is_empty()).then_some(security),
             tags: handler.tags.iter().map(String::from).collect(),
             // Not supported:
             external_docs: Default::default(),
             servers: Default::default(),
             extensions: Default::default(),
             callbacks: Default::default(),
-        };
-
-        let ReferenceOr::Item(path) = state
-            .paths
-            .paths
-            .entry(handler.path.to_string())
-            .or_insert_with(|| ReferenceOr::Item(PathItem::default()))
-        else {
-            unreachable!("We only ever insert ReferenceOr::Item. See above")
-        };
-        let operation_mut = match handler.method {
-            HttpMethod::Get => &mut path.get,
-            HttpMethod::Post => &mut path.post,
-            HttpMethod::Put => &mut path.put,
-            HttpMethod::Delete => &mut path.delete,
-            HttpMethod::Head => &mut path.head,
-            HttpMethod::Options => &mut path.options,
-            HttpMethod::Patch => &mut path.patch,
-            HttpMethod::Trace => &mut path.trace,
-        };
-        *operation_mut = Some(operation);
+        }
     }
 
     /// Returns the [`OpenAPI`] file
@@ -169,6 +395,16 @@ impl SwaggapiPageBuilderImpl {
             license_url,
             version,
             filename: _,
+            servers,
+            security_schemes,
+            security,
+            tags,
+            tag_groups,
+            rejection_responses: _,
+            openapi_version,
+            operation_ids: _,
+            on_duplicate_route: _,
+            strict: _,
             state,
         } = builder;
         let mut guard = state.lock().unwrap();
@@ -178,49 +414,441 @@ impl SwaggapiPageBuilderImpl {
             return open_api;
         }
 
+        let schemas = state.convert_schemas(&mut Vec::new());
+
+        let (paths, _, _) = state.unique_operation_ids(*openapi_version);
+
         let open_api = Arc::new(OpenAPI {
             openapi: "3.0.0".to_string(),
             info: Info {
-                title: title.unwrap_or("Unnamed API").to_string(),
-                description: description.map(str::to_string),
-                terms_of_service: terms_of_service.map(str::to_string),
-                contact: (contact_name.is_some()
-                    || contact_url.is_some()
-                    || contact_email.is_some())
+                title: title.get().unwrap_or("Unnamed API").to_string(),
+                description: description.get().map(str::to_string),
+                terms_of_service: terms_of_service.get().map(str::to_string),
+                contact: (contact_name.get().is_some()
+                    || contact_url.get().is_some()
+                    || contact_email.get().is_some())
                 .then(|| Contact {
-                    name: contact_name.map(str::to_string),
-                    url: contact_url.map(str::to_string),
-                    email: contact_email.map(str::to_string),
+                    name: contact_name.get().map(str::to_string),
+                    url: contact_url.get().map(str::to_string),
+                    email: contact_email.get().map(str::to_string),
                     extensions: Default::default(),
                 }),
-                license: (license_name.is_some() || license_url.is_some()).then(|| License {
-                    name: builder
-                        .license_name
-                        .unwrap_or("Unnamed License")
-                        .to_string(),
-                    url: license_url.map(str::to_string),
-                    extensions: Default::default(),
+                license: (license_name.get().is_some() || license_url.get().is_some()).then(|| {
+                    License {
+                        name: license_name.get().unwrap_or("Unnamed License").to_string(),
+                        url: license_url.get().map(str::to_string),
+                        extensions: Default::default(),
+                    }
                 }),
-                version: version.unwrap_or("v0.0.0").to_string(),
+                version: version.get().unwrap_or("v0.0.0").to_string(),
                 extensions: IndexMap::new(),
             },
-            servers: vec![],
-            paths: state.paths.clone(),
+            servers: servers.iter().map(Server::to_openapi).collect(),
+            paths,
             components: Some(Components {
-                schemas: state
-                    .schemas
+                schemas,
+                security_schemes: state
+                    .security_schemes
                     .iter()
-                    .map(|(key, schema)| (key.clone(), convert_schema(schema.clone())))
+                    .chain(security_schemes.iter().map(|(name, scheme)| (name, scheme)))
+                    .map(|(name, scheme)| {
+                        (name.to_string(), ReferenceOr::Item(scheme.to_openapi()))
+                    })
                     .collect(),
                 ..Default::default()
             }),
-            security: None,
-            tags: vec![],
+            security: (!security.is_empty()).then(|| {
+                security
+                    .iter()
+                    .map(SecurityRequirement::to_openapi)
+                    .collect()
+            }),
+            tags: tags
+                .iter()
+                .chain(
+                    state
+                        .tags
+                        .iter()
+                        .filter(|tag| !tags.iter().any(|known| known.name == tag.name)),
+                )
+                .map(Tag::to_openapi)
+                .collect(),
             external_docs: None,
-            extensions: IndexMap::new(),
+            extensions: if tag_groups.is_empty() {
+                IndexMap::new()
+            } else {
+                IndexMap::from([(
+                    "x-tagGroups".to_string(),
+                    tag_groups.iter().map(TagGroup::to_json).collect(),
+                )])
+            },
         });
 
         state.last_build = Some(open_api.clone());
         open_api
     }
+
+    /// Returns the problems encountered converting the page's schemas into OpenAPI 3.0 ones
+    ///
+    /// If the page is served as OpenAPI 3.1, only the schemas which are inlined
+    /// (i.e. not in `components/schemas`) are converted and checked.
+    pub fn diagnostics(builder: &SwaggapiPageBuilder) -> Vec<ConversionDiagnostic> {
+        let mut guard = builder.state.lock().unwrap();
+        let state = guard.get_or_insert_with(Default::default);
+
+        let mut diagnostics = state.diagnostics.clone();
+        if builder.openapi_version == OpenApiVersion::V3_0 {
+            state.convert_schemas(&mut diagnostics);
+        }
+        diagnostics
+    }
+
+    /// Returns an error containing the [`SwaggapiPageBuilderImpl::diagnostics`] if there are any
+    pub fn validate(builder: &SwaggapiPageBuilder) -> Result<(), Vec<ConversionDiagnostic>> {
+        let diagnostics = Self::diagnostics(builder);
+        if diagnostics.is_empty() {
+            Ok(())
+        } else {
+            Err(diagnostics)
+        }
+    }
+
+    /// Returns an error containing the [`SwaggapiPageBuilderImpl::duplicate_routes`]
+    /// if there are any and they are configured to be an [`DuplicateRouteBehavior::Error`]
+    pub fn validate_routes(builder: &SwaggapiPageBuilder) -> Result<(), Vec<DuplicateRoute>> {
+        let duplicates = Self::duplicate_routes(builder);
+        if builder.on_duplicate_route == DuplicateRouteBehavior::Error && !duplicates.is_empty() {
+            Err(duplicates)
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Panics if [`SwaggapiPageBuilderImpl::validate_routes`] fails
+    /// or the page is [`strict`](SwaggapiPageBuilder::strict) and [`SwaggapiPageBuilderImpl::validate`] fails
+    /// or a handler has several arguments consuming the request body
+    /// or an explicit `operationId` had to be renamed
+    pub(crate) fn assert_valid(builder: &SwaggapiPageBuilder) {
+        if let Err(duplicates) = Self::validate_routes(builder) {
+            panic!(
+                "The api page {:?} has several handlers for the same route:\n{}",
+                builder.title.get().unwrap_or("Unnamed API"),
+                duplicates
+                    .iter()
+                    .map(DuplicateRoute::to_string)
+                    .collect::<Vec<_>>()
+                    .join("\n")
+            );
+        }
+        if !builder.strict {
+            return;
+        }
+        if let Err(diagnostics) = Self::validate(builder) {
+            panic!(
+                "The api page {:?} lost information converting its schemas:\n{}",
+                builder.title.get().unwrap_or("Unnamed API"),
+                diagnostics
+                    .iter()
+                    .map(ConversionDiagnostic::to_string)
+                    .collect::<Vec<_>>()
+                    .join("\n")
+            );
+        }
+        let conflicts = Self::conflicting_request_bodies(builder);
+        if !conflicts.is_empty() {
+            panic!(
+                "The api page {:?} has handlers with several arguments consuming the request body:\n{}",
+                builder.title.get().unwrap_or("Unnamed API"),
+                conflicts
+                    .iter()
+                    .map(ConflictingRequestBodies::to_string)
+                    .collect::<Vec<_>>()
+                    .join("\n")
+            );
+        }
+        let collisions = Self::duplicate_operation_ids(builder)
+            .into_iter()
+            .filter(|duplicate| duplicate.explicit)
+            .collect::<Vec<_>>();
+        if !collisions.is_empty() {
+            panic!(
+                "The api page {:?} has several handlers with the same explicit operationId:\n{}",
+                builder.title.get().unwrap_or("Unnamed API"),
+                collisions
+                    .iter()
+                    .map(DuplicateOperationId::to_string)
+                    .collect::<Vec<_>>()
+                    .join("\n")
+            );
+        }
+    }
+
+    /// Returns the handlers which were added for an already documented path and method
+    pub fn duplicate_routes(builder: &SwaggapiPageBuilder) -> Vec<DuplicateRoute> {
+        builder
+            .state
+            .lock()
+            .unwrap()
+            .as_ref()
+            .map(|state| state.duplicate_routes.clone())
+            .unwrap_or_default()
+    }
+
+    /// Returns the handlers which have several arguments consuming the request body
+    pub fn conflicting_request_bodies(
+        builder: &SwaggapiPageBuilder,
+    ) -> Vec<ConflictingRequestBodies> {
+        builder
+            .state
+            .lock()
+            .unwrap()
+            .as_ref()
+            .map(|state| state.conflicting_request_bodies.clone())
+            .unwrap_or_default()
+    }
+
+    /// Returns the handlers whose derived `operationId` was already used and had to be renamed
+    pub fn duplicate_operation_ids(builder: &SwaggapiPageBuilder) -> Vec<DuplicateOperationId> {
+        builder
+            .state
+            .lock()
+            .unwrap()
+            .as_ref()
+            .map(|state| state.unique_operation_ids(builder.openapi_version).2)
+            .unwrap_or_default()
+    }
+
+    /// Returns the paths and webhooks with unique `operationId`s and the renamed operations
+    ///
+    /// Explicit `operationId`s are kept, derived ones are suffixed with a number if they are already used.
+    /// An explicit `operationId` set by several operations is only kept by the first of them.
+    /// Webhooks are only considered for OpenAPI 3.1 which is the only version including them.
+    fn unique_operation_ids(
+        &self,
+        version: OpenApiVersion,
+    ) -> (Paths, IndexMap<String, PathItem>, Vec<DuplicateOperationId>) {
+        let mut paths = self.paths.clone();
+        let mut webhooks = match version {
+            OpenApiVersion::V3_0 => IndexMap::new(),
+            OpenApiVersion::V3_1 => self.webhooks.clone(),
+        };
+
+        // Sort the operations to not depend on the order the handlers were added in
+        let mut items = paths
+            .paths
+            .iter_mut()
+            .filter_map(|(path, item)| match item {
+                ReferenceOr::Item(item) => Some((false, path.as_str(), item)),
+                ReferenceOr::Reference { .. } => None,
+            })
+            .chain(
+                webhooks
+                    .iter_mut()
+                    .map(|(name, item)| (true, name.as_str(), item)),
+            )
+            .collect::<Vec<_>>();
+        items.sort_by(|(a_webhook, a_name, _), (b_webhook, b_name, _)| {
+            (a_webhook, a_name).cmp(&(b_webhook, b_name))
+        });
+
+        let is_explicit = |operation_id: &str, location: &str| {
+            self.explicit_operation_ids
+                .get(location)
+                .is_some_and(|explicit| explicit == operation_id)
+        };
+
+        // The first operation setting an explicit `operationId` keeps it
+        let mut used = HashSet::new();
+        let mut kept = HashSet::new();
+        for (_, name, item) in &mut items {
+            for method in METHODS {
+                let location = format!("{method} {name}");
+                let Some(operation_id) = operation_mut(item, method)
+                    .as_ref()
+                    .and_then(|operation| operation.operation_id.as_ref())
+                else {
+                    continue;
+                };
+                if is_explicit(operation_id, &location) && used.insert(operation_id.clone()) {
+                    kept.insert(location);
+                }
+            }
+        }
+
+        let mut duplicates = Vec::new();
+        for (_, name, item) in &mut items {
+            for method in METHODS {
+                let location = format!("{method} {name}");
+                let Some(operation_id) = operation_mut(item, method)
+                    .as_mut()
+                    .and_then(|operation| operation.operation_id.as_mut())
+                else {
+                    continue;
+                };
+                if kept.contains(&location) {
+                    continue;
+                }
+                if used.contains(operation_id.as_str()) {
+                    let unique = (2..)
+                        .map(|number| format!("{operation_id}_{number}"))
+                        .find(|candidate| !used.contains(candidate))
+                        .expect("There are infinitely many numbers");
+                    duplicates.push(DuplicateOperationId {
+                        explicit: is_explicit(operation_id, &location),
+                        operation: location,
+                        operation_id: mem::replace(operation_id, unique.clone()),
+                        renamed: unique,
+                    });
+                }
+                used.insert(operation_id.clone());
+            }
+        }
+
+        (paths, webhooks, duplicates)
+    }
+
+    /// Converts the schemas stored in `components/schemas`
+    fn convert_schemas(
+        &self,
+        diagnostics: &mut Vec<ConversionDiagnostic>,
+    ) -> IndexMap<String, ReferenceOr<openapiv3::Schema>> {
+        self.schemas
+            .iter()
+            .map(|(key, schema)| {
+                let schema = convert_schema_with_diagnostics(schema.clone(), key, diagnostics);
+                (key.clone(), schema)
+            })
+            .collect()
+    }
+
+    /// Returns the OpenAPI document in the version set by [`SwaggapiPageBuilder::openapi_version`]
+    ///
+    /// The build operation is cached (hence the `Arc`) so feel free to call this eagerly.
+    pub fn build_json(builder: &SwaggapiPageBuilder) -> Arc<Value> {
+        Self::build_json_documents(builder).0
+    }
+
+    /// Returns the [`SwaggapiPageBuilderImpl::build_json`] document with its keys in the order they were added
+    ///
+    /// This is the document served by the [`SwaggerUi`](crate::SwaggerUi).
+    #[cfg(any(feature = "actix", feature = "axum"))]
+    pub(crate) fn build_ordered_json(builder: &SwaggapiPageBuilder) -> Arc<OrderedJson> {
+        Self::build_json_documents(builder).1
+    }
+
+    /// Builds (or gets the cached) documents returned by
+    /// [`SwaggapiPageBuilderImpl::build_json`] and [`SwaggapiPageBuilderImpl::build_ordered_json`]
+    fn build_json_documents(builder: &SwaggapiPageBuilder) -> (Arc<Value>, Arc<OrderedJson>) {
+        loop {
+            let generation = {
+                let mut guard = builder.state.lock().unwrap();
+                let state = guard.get_or_insert_with(Default::default);
+                if let Some(documents) = state.last_json_build.clone() {
+                    return documents;
+                }
+                state.generation
+            };
+
+            let open_api = Self::build(builder);
+
+            let mut guard = builder.state.lock().unwrap();
+            let state = guard.get_or_insert_with(Default::default);
+            // The page changed while the lock was released, so `open_api` might be outdated
+            if state.generation != generation {
+                continue;
+            }
+
+            let mut order = OrderedJson::new(&*open_api);
+            let mut json =
+                serde_json::to_value(&*open_api).expect("OpenAPI should serialize to json");
+            if builder.openapi_version == OpenApiVersion::V3_1 {
+                json["openapi"] = Value::from("3.1.0");
+                json["components"]["schemas"] = state
+                    .schemas
+                    .iter()
+                    .map(|(key, schema)| {
+                        let schema =
+                            serde_json::to_value(schema).expect("Schema should serialize to json");
+                        (key.clone(), schema)
+                    })
+                    .collect();
+                let (_, webhooks, _) = state.unique_operation_ids(builder.openapi_version);
+                if !webhooks.is_empty() {
+                    json["webhooks"] =
+                        serde_json::to_value(&webhooks).expect("Webhooks should serialize to json");
+                    if let OrderedJson::Object(order) = &mut order {
+                        order.insert("webhooks".to_string(), OrderedJson::new(&webhooks));
+                    }
+                }
+                upgrade_to_3_1(&mut json);
+            }
+
+            let ordered = Arc::new(OrderedJson::reordered(&json, &order));
+            let json = Arc::new(json);
+            state.last_json_build = Some((json.clone(), ordered.clone()));
+            return (json, ordered);
+        }
+    }
+}
+
+/// Derive a handler's `operationId` using `strategy` unless it has set one explicitly
+fn operation_id(strategy: OperationIdStrategy, handler: &ContextHandler) -> String {
+    if let Some(operation_id) = handler.operation_id {
+        return operation_id.to_string();
+    }
+    let prefix = match strategy {
+        OperationIdStrategy::Ident => None,
+        OperationIdStrategy::ModulePath => {
+            let mut segments = handler.module_path.split("::").skip(1).peekable();
+            segments
+                .peek()
+                .is_some()
+                .then(|| segments.collect::<Vec<_>>().join("_"))
+        }
+        OperationIdStrategy::Tag => handler.tags.iter().min().map(str::to_string),
+    };
+    match prefix {
+        Some(prefix) => format!("{prefix}_{}", handler.ident),
+        None => handler.ident.to_string(),
+    }
+}
+
+/// Matches a path parameter like `{id}`
+fn path_param_regex() -> &'static Regex {
+    static PATH_PARAM_REGEX: OnceLock<Regex> = OnceLock::new();
+    PATH_PARAM_REGEX.get_or_init(|| Regex::new(r"\{[^}]*}").unwrap())
+}
+
+/// Removes the names of a path's parameters
+///
+/// Two paths which only differ in their parameters' names (`/users/{id}` and `/users/{user_id}`)
+/// match the same requests and are therefore the same route.
+fn normalize_path(path: &str) -> String {
+    path_param_regex().replace_all(path, "{}").into_owned()
+}
+
+/// The methods of a [`PathItem`] in the order they are documented
+const METHODS: [HttpMethod; 8] = [
+    HttpMethod::Get,
+    HttpMethod::Put,
+    HttpMethod::Post,
+    HttpMethod::Delete,
+    HttpMethod::Options,
+    HttpMethod::Head,
+    HttpMethod::Patch,
+    HttpMethod::Trace,
+];
+
+/// Get the field of a [`PathItem`] storing the [`Operation`] for `method`
+fn operation_mut(path: &mut PathItem, method: HttpMethod) -> &mut Option<Operation> {
+    match method {
+        HttpMethod::Get => &mut path.get,
+        HttpMethod::Post => &mut path.post,
+        HttpMethod::Put => &mut path.put,
+        HttpMethod::Delete => &mut path.delete,
+        HttpMethod::Head => &mut path.head,
+        HttpMethod::Options => &mut path.options,
+        HttpMethod::Patch => &mut path.patch,
+        HttpMethod::Trace => &mut path.trace,
+    }
 }
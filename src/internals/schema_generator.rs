@@ -1,3 +1,4 @@
+use std::any::type_name;
 use std::mem;
 
 use openapiv3::ObjectType;
@@ -11,13 +12,14 @@ use schemars::gen::SchemaSettings;
 use schemars::JsonSchema;
 use schemars::Map;
 
-use crate::internals::convert_schema;
+use crate::diagnostics::ConversionDiagnostic;
+use crate::internals::convert_schema_with_diagnostics;
 
 /// State for generating schemas from types implementing [`JsonSchema`]
 ///
 /// If you require the underlying [`SchemaGenerator` from `schemars`](schemars::gen::SchemaGenerator),
 /// you can use [`AsRef`] and [`AsMut`] to gain access.
-pub struct SchemaGenerator(InnerGenerator);
+pub struct SchemaGenerator(InnerGenerator, Vec<ConversionDiagnostic>);
 impl AsRef<InnerGenerator> for SchemaGenerator {
     fn as_ref(&self) -> &InnerGenerator {
         &self.0
@@ -34,7 +36,8 @@ impl SchemaGenerator {
     /// This might do nothing but return a reference to the schema
     /// already added to the generator previously.
     pub fn generate<T: JsonSchema>(&mut self) -> ReferenceOr<Schema> {
-        convert_schema(self.0.subschema_for::<T>())
+        let schema = self.0.subschema_for::<T>();
+        convert_schema_with_diagnostics(schema, type_name::<T>(), &mut self.1)
     }
 
     /// Generate an openapi schema for the type `T`
@@ -49,7 +52,8 @@ impl SchemaGenerator {
     /// Returns `Err`, if `T::json_schema` does not uphold this behaviour.
     /// The `String` in the `Err` will be the reference which should not have been returned.
     pub fn generate_refless<T: JsonSchema>(&mut self) -> Result<Schema, String> {
-        let schema = convert_schema(T::json_schema(&mut self.0));
+        let schema = T::json_schema(&mut self.0);
+        let schema = convert_schema_with_diagnostics(schema, type_name::<T>(), &mut self.1);
         match schema {
             ReferenceOr::Item(schema) => Ok(schema),
             ReferenceOr::Reference { reference } => Err(reference),
@@ -84,14 +88,18 @@ impl SchemaGenerator {
     ///
     /// This requires some cleanup which is guaranteed by running a `FnOnce`
     /// instead of giving ownership of `SchemaGenerator` directly.
+    ///
+    /// Problems converting the schemas which are not stored in `definitions`
+    /// are added to `diagnostics`.
     pub fn employ<T>(
         definitions: &mut Map<String, schemars::schema::Schema>,
+        diagnostics: &mut Vec<ConversionDiagnostic>,
         func: impl FnOnce(&mut Self) -> T,
     ) -> T {
         // Construct new empty generator
         let mut settings = SchemaSettings::openapi3();
         settings.visitors = Vec::new();
-        let mut gen = Self(InnerGenerator::new(settings));
+        let mut gen = Self(InnerGenerator::new(settings), Vec::new());
 
         // Give the `definitions` to the generator for him to extend
         *gen.as_mut().definitions_mut() = mem::take(definitions);
@@ -102,6 +110,12 @@ impl SchemaGenerator {
         // Take the (potentially modified) `definitions` back
         *definitions = gen.as_mut().take_definitions();
 
+        for diagnostic in gen.1 {
+            if !diagnostics.contains(&diagnostic) {
+                diagnostics.push(diagnostic);
+            }
+        }
+
         output
     }
 }
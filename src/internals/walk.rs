@@ -0,0 +1,261 @@
+use std::sync::OnceLock;
+
+use serde_json::Map;
+use serde_json::Value;
+
+/// The keys of a path item's operations in the order they are documented
+pub(crate) const METHODS: [&str; 8] = [
+    "get", "put", "post", "delete", "options", "head", "patch", "trace",
+];
+
+/// The kind of object [`walk_document`] visits
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub(crate) enum Position {
+    /// A schema object or a sub schema
+    Schema,
+
+    /// Any other object of the OpenAPI structure (for example a parameter or a response)
+    ///
+    /// Objects which might be references are always visited,
+    /// but payloads like examples and default values are never entered.
+    Other,
+}
+
+/// Walks the objects of an OpenAPI document and calls `visit` on each
+///
+/// Only positions defined by the OpenAPI specification are entered,
+/// user provided payloads (`example`, `examples`' values, `default`, `enum`, `const`, extensions) are skipped.
+/// Sub schemas are visited before the schema containing them.
+///
+/// `visit` receives the [JSON pointer](https://datatracker.ietf.org/doc/html/rfc6901) to the object.
+/// Both OpenAPI 3.0 and 3.1 documents are supported.
+pub(crate) fn walk_document(document: &Value, visit: &mut impl FnMut(Position, &str, &Value)) {
+    let mut walker = Walker {
+        pointer: String::new(),
+        visit,
+    };
+    walker.each_entry(document, "paths", Walker::path_item);
+    walker.each_value(document, "webhooks", Walker::path_item);
+    walker.field(document, "components", |walker, components| {
+        walker.each_value(components, "schemas", Walker::schema);
+        walker.each_value(components, "responses", Walker::response);
+        walker.each_value(components, "parameters", Walker::parameter);
+        walker.each_value(components, "examples", Walker::other);
+        walker.each_value(components, "requestBodies", Walker::request_body);
+        walker.each_value(components, "headers", Walker::parameter);
+        walker.each_value(components, "securitySchemes", Walker::other);
+        walker.each_value(components, "links", Walker::other);
+        walker.each_value(components, "callbacks", Walker::callback);
+        walker.each_value(components, "pathItems", Walker::path_item);
+    });
+}
+
+/// Like [`walk_document`] but lets `visit` modify the objects
+///
+/// The objects to visit are determined before any of them is modified.
+/// Since sub objects are visited before the object containing them,
+/// `visit` changing an object doesn't affect the ones visited after it.
+pub(crate) fn walk_document_mut(
+    document: &mut Value,
+    visit: &mut impl FnMut(Position, &str, &mut Value),
+) {
+    let mut positions = Vec::new();
+    walk_document(document, &mut |position, pointer, _| {
+        positions.push((position, pointer.to_string()));
+    });
+    for (position, pointer) in positions {
+        if let Some(value) = document.pointer_mut(&pointer) {
+            visit(position, &pointer, value);
+        }
+    }
+}
+
+struct Walker<'a, F> {
+    pointer: String,
+    visit: &'a mut F,
+}
+
+impl<F: FnMut(Position, &str, &Value)> Walker<'_, F> {
+    /// Calls `func` on `value[key]` if it exists
+    fn field(&mut self, value: &Value, key: &str, func: impl FnOnce(&mut Self, &Value)) {
+        if let Some(child) = value.get(key) {
+            let len = self.pointer.len();
+            self.pointer.push('/');
+            self.pointer.push_str(&escape(key));
+            func(self, child);
+            self.pointer.truncate(len);
+        }
+    }
+
+    /// Calls `func` on every child of the object or array `value[key]`
+    fn each_value(&mut self, value: &Value, key: &str, func: impl FnMut(&mut Self, &Value)) {
+        self.field(value, key, |walker, children| {
+            walker.children(children, false, func)
+        });
+    }
+
+    /// Calls `func` on every child of the object `value[key]` except for extensions
+    ///
+    /// Used for the objects which are maps and may contain extensions (`paths`, `responses` and callbacks).
+    fn each_entry(&mut self, value: &Value, key: &str, func: impl FnMut(&mut Self, &Value)) {
+        self.field(value, key, |walker, children| {
+            walker.children(children, true, func)
+        });
+    }
+
+    /// Calls `func` on every child of the object or array `value`
+    fn children(
+        &mut self,
+        value: &Value,
+        skip_extensions: bool,
+        mut func: impl FnMut(&mut Self, &Value),
+    ) {
+        let children: Vec<(String, &Value)> = match value {
+            Value::Object(object) => object
+                .iter()
+                .filter(|(key, _)| !(skip_extensions && key.starts_with("x-")))
+                .map(|(key, child)| (escape(key), child))
+                .collect(),
+            Value::Array(array) => array
+                .iter()
+                .enumerate()
+                .map(|(index, child)| (index.to_string(), child))
+                .collect(),
+            _ => Vec::new(),
+        };
+        for (token, child) in children {
+            let len = self.pointer.len();
+            self.pointer.push('/');
+            self.pointer.push_str(&token);
+            func(self, child);
+            self.pointer.truncate(len);
+        }
+    }
+
+    fn other(&mut self, value: &Value) {
+        (self.visit)(Position::Other, &self.pointer, value);
+    }
+
+    fn path_item(&mut self, item: &Value) {
+        self.each_value(item, "parameters", Self::parameter);
+        for method in METHODS {
+            self.field(item, method, Self::operation);
+        }
+        self.other(item);
+    }
+
+    fn operation(&mut self, operation: &Value) {
+        self.each_value(operation, "parameters", Self::parameter);
+        self.field(operation, "requestBody", Self::request_body);
+        self.each_entry(operation, "responses", Self::response);
+        self.each_value(operation, "callbacks", Self::callback);
+        self.other(operation);
+    }
+
+    fn callback(&mut self, callback: &Value) {
+        if callback.get("$ref").is_some() {
+            self.other(callback);
+        } else {
+            self.children(callback, true, Self::path_item);
+        }
+    }
+
+    /// Parameter or header object
+    fn parameter(&mut self, parameter: &Value) {
+        self.field(parameter, "schema", Self::schema);
+        self.each_value(parameter, "content", Self::media_type);
+        self.each_value(parameter, "examples", Self::other);
+        self.other(parameter);
+    }
+
+    fn request_body(&mut self, body: &Value) {
+        self.each_value(body, "content", Self::media_type);
+        self.other(body);
+    }
+
+    fn response(&mut self, response: &Value) {
+        self.each_value(response, "headers", Self::parameter);
+        self.each_value(response, "content", Self::media_type);
+        self.each_value(response, "links", Self::other);
+        self.other(response);
+    }
+
+    fn media_type(&mut self, media: &Value) {
+        self.field(media, "schema", Self::schema);
+        self.each_value(media, "examples", Self::other);
+        self.each_value(media, "encoding", |walker, encoding| {
+            walker.each_value(encoding, "headers", Self::parameter);
+        });
+    }
+
+    fn schema(&mut self, schema: &Value) {
+        if !schema.is_object() {
+            // Boolean schemas (3.1) don't contain anything
+            return;
+        }
+        for keyword in [
+            "properties",
+            "patternProperties",
+            "dependentSchemas",
+            "$defs",
+            "definitions",
+        ] {
+            self.each_value(schema, keyword, Self::schema);
+        }
+        for keyword in ["allOf", "anyOf", "oneOf", "prefixItems"] {
+            self.each_value(schema, keyword, Self::schema);
+        }
+        for keyword in [
+            "items",
+            "additionalProperties",
+            "not",
+            "if",
+            "then",
+            "else",
+            "contains",
+            "propertyNames",
+            "unevaluatedItems",
+            "unevaluatedProperties",
+        ] {
+            self.field(schema, keyword, |walker, sub_schema| {
+                // `items` may still be an array in old JSON schema drafts
+                if sub_schema.is_array() {
+                    walker.children(sub_schema, false, Self::schema);
+                } else {
+                    walker.schema(sub_schema);
+                }
+            });
+        }
+        (self.visit)(Position::Schema, &self.pointer, schema);
+    }
+}
+
+/// Escapes a string to be used as a single token in a JSON pointer
+pub(crate) fn escape(token: &str) -> String {
+    token.replace('~', "~0").replace('/', "~1")
+}
+
+/// Follows `value`'s `$ref` (if any) to the object in `root`
+///
+/// Returns [`Value::Null`] if the reference can't be resolved.
+pub(crate) fn resolve<'a>(root: &'a Value, mut value: &'a Value) -> &'a Value {
+    // Limit the number of references followed to guard against cycles
+    for _ in 0..32 {
+        let Some(reference) = value.get("$ref").and_then(Value::as_str) else {
+            return value;
+        };
+        value = reference
+            .strip_prefix('#')
+            .and_then(|pointer| root.pointer(pointer))
+            .unwrap_or(&Value::Null);
+    }
+    &Value::Null
+}
+
+/// Get `value` as object or an empty one
+pub(crate) fn object(value: &Value) -> &Map<String, Value> {
+    static EMPTY: OnceLock<Map<String, Value>> = OnceLock::new();
+    value
+        .as_object()
+        .unwrap_or_else(|| EMPTY.get_or_init(Map::new))
+}
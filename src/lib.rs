@@ -2,11 +2,23 @@
 #![warn(clippy::todo)]
 
 pub mod as_responses;
+#[cfg(feature = "cli")]
+pub mod cli;
+pub mod compatibility;
 mod context;
+pub mod diagnostics;
 pub mod handler_argument;
+pub mod headers;
 pub mod internals;
 mod macro_docs;
+#[cfg(feature = "multipart")]
+pub mod multipart;
 mod page;
+mod registry;
+pub mod security;
+pub mod servers;
+pub mod snapshot;
+pub mod tags;
 #[cfg(feature = "swagger-ui")]
 mod swagger;
 pub mod utils;
@@ -14,9 +26,15 @@ pub mod utils;
 pub use macro_docs::*;
 
 pub use self::context::ApiContext;
+pub use self::page::DuplicateRouteBehavior;
+pub use self::page::OpenApiVersion;
+pub use self::page::OperationIdStrategy;
 pub use self::page::PageOfEverything;
+pub use self::page::PageSnapshot;
+pub use self::page::RuntimePage;
 pub use self::page::SwaggapiPage;
 pub use self::page::SwaggapiPageBuilder;
+pub use self::registry::Registry;
 #[cfg(feature = "swagger-ui")]
 pub use self::swagger::SwaggerUi;
 
@@ -24,7 +24,7 @@ pub use swaggapi_macro::get;
 /// /// Deletes the entire application's state
 /// ///
 /// /// This endpoint is very dangerous and can only be used by admins.
-/// #[swaggapi::delete("/deleteAll", tags("admin", "dangerous"))]
+/// #[swaggapi::delete("/deleteAll", tags("admin", "dangerous"), security("bearer"))]
 /// async fn delete_all() -> () {
 ///     // ...
 /// }
@@ -47,6 +47,32 @@ pub use swaggapi_macro::get;
 ///     - optional
 ///     - list of string literal, for example `tags("foo", "bar)`
 ///
+/// - `security`: A list of alternative [`SecurityRequirement`](crate::security::SecurityRequirement)s
+///
+///     A string literal is a shorthand for a requirement of the single security scheme with this name.
+///
+///     The schemes have to be declared on the page using [`SwaggapiPageBuilder::security_schemes`](crate::SwaggapiPageBuilder::security_schemes).
+///     - optional
+///     - list of string literals or `SecurityRequirement` expressions, for example `security("bearer", SecurityRequirement::new(&[("oauth", &["admin"])]))`
+///
+/// - `operation_id`: The `operationId` identifying the handler in generated clients
+///
+///     This defaults to the function's identifier possibly prefixed according to [`SwaggapiPageBuilder::operation_ids`](crate::SwaggapiPageBuilder::operation_ids).
+///
+///     Derived ids which collide are renamed (see [`SwaggapiPage::duplicate_operation_ids`](crate::SwaggapiPage::duplicate_operation_ids)), explicit ones only if two handlers on a page set the same id, which is an error for [`strict`](crate::SwaggapiPageBuilder::strict) pages.
+///     - optional
+///     - a string literal, for example `operation_id = "listUsers"`
+///
+/// - `state`: The [axum](::axum) state required by the handler
+///
+///     This defaults to the `S` of an argument of type `State<S>` or `()` if there is no such argument.
+///
+///     Set it explicitly if the state is required by some other extractor, for example when using `State<SubState>` where `SubState` is extracted from the router's state through [`FromRef`](::axum::extract::FromRef).
+///
+///     Adding a handler to an [`ApiContext`](crate::ApiContext) with a different state panics.
+///     - optional
+///     - a type, for example `state = AppState` or `state(crate::AppState)`
+///
 /// ## Positional arguments
 /// Since `method` and `path` are required, they can alternatively be passed as positional arguments:
 /// - `#[handler(Get, "/")]`
@@ -94,6 +120,81 @@ pub use swaggapi_macro::put;
 /// Unlike `#[handler]` it uses the http method `TRACE`,
 /// for everything else please refer to [``#[handler]``](handler)
 pub use swaggapi_macro::trace;
+/// Derives [`AsResponses`](trait@crate::as_responses::AsResponses) for an enum, usually an error type
+///
+/// Every variant has to be annotated with `#[response(status = ...)]`:
+/// - `status`: the status code the variant is sent with
+/// - `description`: a short description of the response
+///
+///     - optional, defaults to the variant's doc comment
+///
+/// A variant with a single unnamed field is described by the field's [`JsonSchema`](schemars::JsonSchema)
+/// as JSON body, a unit variant as an empty response.
+/// Variants sharing a status code are combined using `oneOf`.
+///
+/// This only describes the responses,
+/// the enum still has to implement the framework's `IntoResponse` / `ResponseError` itself.
+///
+/// ```rust
+/// # use schemars::JsonSchema;
+/// # use swaggapi::AsResponses;
+/// #[derive(JsonSchema)]
+/// struct Conflict {
+///     existing: u64,
+/// }
+///
+/// #[derive(AsResponses)]
+/// enum ApiError {
+///     /// The requested resource doesn't exist
+///     #[response(status = 404)]
+///     NotFound,
+///
+///     #[response(status = 409, description = "The resource already exists")]
+///     Conflict(Conflict),
+///
+///     #[response(status = 409, description = "The resource has been modified")]
+///     Modified(String),
+///
+///     #[response(status = 500, description = "Internal server error")]
+///     Internal,
+/// }
+/// ```
+pub use swaggapi_macro::AsResponses;
+/// Derives [`FromHeaders`](trait@crate::headers::FromHeaders) for a struct with named fields
+///
+/// Each field is a header named after the field with `_` replaced by `-`.
+/// The field's type has to implement [`HeaderField`](crate::headers::HeaderField)
+/// which is the case for strings, numbers, `bool` and `Option<T>` for optional headers.
+///
+/// The struct also has to implement [`JsonSchema`](schemars::JsonSchema)
+/// which is used to describe the headers.
+/// Each field is looked up in the schema by the name serde gives it,
+/// so `#[serde(rename)]` and `#[serde(rename_all)]` are respected.
+/// They don't change the header names though.
+///
+/// Fields can be annotated with:
+/// - `#[header(name = "X-Custom")]`: to use a different header name
+/// - `#[cookie]`: to read a cookie named after the field instead of a header
+/// - `#[cookie(name = "custom")]`: to read a cookie with a different name
+///
+/// See [`headers`](crate::headers) for an example.
+pub use swaggapi_macro::FromHeaders;
+/// Derives [`FromMultipart`](trait@crate::multipart::FromMultipart) for a struct with named fields
+///
+/// Each field is a part of the `multipart/form-data` request body named after the field.
+/// The field's type has to implement [`MultipartField`](crate::multipart::MultipartField):
+/// - [`String`] for a text part
+/// - [`FileUpload`](crate::multipart::FileUpload) for a file part
+/// - `Option<T>` for an optional part
+/// - `Vec<T>` for any number of parts sharing the same name
+///
+/// Like with serde, a part can be renamed using `#[serde(rename = "...")]`
+/// and all parts using `#[serde(rename_all = "...")]` on the struct.
+/// A field's doc comment becomes the part's description.
+///
+/// See [`multipart`](crate::multipart) for an example.
+#[cfg(feature = "multipart")]
+pub use swaggapi_macro::FromMultipart;
 /// Derives [`SwaggapiPage`](trait@crate::SwaggapiPage) for a unit struct
 ///
 /// ```rust
@@ -106,9 +207,21 @@ pub use swaggapi_macro::trace;
 /// ## Arguments
 /// are passed through the helper `#[page(...)]`.
 ///
-/// All arguments are of shape `key = "value"`
+/// All arguments are of shape `key = value`
 /// where the list of keys and their description
 /// can be taken from [`SwaggapiPageBuilder`](crate::SwaggapiPageBuilder)'s methods.
+/// The value can be any const expression the builder method accepts:
+///
+/// ```rust
+/// # use swaggapi_macro::SwaggapiPage;
+/// # use swaggapi::security::SecurityScheme;
+/// #[derive(SwaggapiPage)]
+/// #[page(
+///     title = "My authenticated api",
+///     security_schemes = &[("bearer", SecurityScheme::bearer())],
+/// )]
+/// struct MyAuthenticatedApi;
+/// ```
 ///
 /// ### Noteworthy differences from the builder:
 /// - `filename` will default to the struct's identifier (followed by `.json`)
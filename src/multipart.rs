@@ -0,0 +1,415 @@
+//! Typed `multipart/form-data` request bodies
+//!
+//! Use [`#[derive(FromMultipart)]`](macro@crate::FromMultipart) on a struct
+//! and accept it in your handler wrapped in [`TypedMultipart`]:
+//!
+//! ```rust
+//! # use swaggapi::FromMultipart;
+//! # use swaggapi::multipart::FileUpload;
+//! # use swaggapi::multipart::TypedMultipart;
+//! #[derive(FromMultipart)]
+//! struct Upload {
+//!     /// A text part
+//!     title: String,
+//!
+//!     /// A file part
+//!     file: FileUpload,
+//!
+//!     /// Any number of file parts sharing the same name
+//!     attachments: Vec<FileUpload>,
+//! }
+//!
+//! #[swaggapi::post("/upload")]
+//! async fn upload(upload: TypedMultipart<Upload>) -> String {
+//!     upload.0.title
+//! }
+//! ```
+
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt;
+
+use bytes::Bytes;
+use openapiv3::ArrayType;
+use openapiv3::ReferenceOr;
+use openapiv3::Schema;
+use openapiv3::SchemaKind;
+use openapiv3::StringFormat;
+use openapiv3::StringType;
+use openapiv3::Type;
+use openapiv3::VariantOrUnknownOrEmpty;
+
+use crate::internals::SchemaGenerator;
+
+/// Extractor for a `multipart/form-data` request body parsed into `T`
+///
+/// `T` is usually implemented using [`#[derive(FromMultipart)]`](macro@crate::FromMultipart).
+#[derive(Debug)]
+pub struct TypedMultipart<T>(pub T);
+
+/// A type which can be constructed from the parts of a `multipart/form-data` request body
+///
+/// This trait should be implemented using [`#[derive(FromMultipart)]`](macro@crate::FromMultipart).
+pub trait FromMultipart: Sized {
+    /// Describe the parts `Self` is constructed from
+    fn parts(gen: &mut SchemaGenerator) -> Vec<MultipartPart>;
+
+    /// Construct `Self` from the request's parts
+    fn from_multipart(parts: MultipartParts) -> Result<Self, MultipartError>;
+}
+
+/// Description of a single part in a [`FromMultipart`] type
+pub struct MultipartPart {
+    /// The part's name
+    pub name: &'static str,
+
+    /// The part's description (taken from the field's doc comment)
+    pub description: Option<&'static str>,
+
+    /// The part's schema
+    pub schema: ReferenceOr<Schema>,
+
+    /// Is the part required?
+    pub required: bool,
+
+    /// The part's content type used in the request body's `encoding`
+    pub content_type: &'static str,
+}
+
+/// A field in a [`FromMultipart`] type
+pub trait MultipartField: Sized {
+    /// Get the schema describing the field
+    fn schema(gen: &mut SchemaGenerator) -> ReferenceOr<Schema>;
+
+    /// Is the field required?
+    fn required() -> bool {
+        true
+    }
+
+    /// The field's content type
+    fn content_type() -> &'static str;
+
+    /// Construct the field from all parts sharing its `name`
+    fn from_parts(name: &'static str, parts: Vec<FileUpload>) -> Result<Self, MultipartError>;
+}
+
+/// A single part of a `multipart/form-data` request body
+#[derive(Clone, Debug)]
+pub struct FileUpload {
+    /// The filename provided in the part's `Content-Disposition` header
+    pub file_name: Option<String>,
+
+    /// The part's `Content-Type` header
+    pub content_type: Option<String>,
+
+    /// The part's content
+    pub data: Bytes,
+}
+
+/// The parts of a `multipart/form-data` request body grouped by their names
+#[derive(Debug, Default)]
+pub struct MultipartParts(HashMap<String, Vec<FileUpload>>);
+
+impl MultipartParts {
+    /// Adds a part
+    pub fn push(&mut self, name: String, part: FileUpload) {
+        self.0.entry(name).or_default().push(part);
+    }
+
+    /// Removes all parts called `name` and constructs a field from them
+    pub fn take<F: MultipartField>(&mut self, name: &'static str) -> Result<F, MultipartError> {
+        F::from_parts(name, self.0.remove(name).unwrap_or_default())
+    }
+}
+
+/// Error produced while constructing a [`FromMultipart`] type
+#[derive(Debug)]
+pub enum MultipartError {
+    /// A required part is missing
+    MissingPart(&'static str),
+
+    /// A part which may only appear once appeared several times
+    DuplicatePart(&'static str),
+
+    /// A text part is not valid utf-8
+    InvalidText(&'static str),
+
+    /// The parts exceed the contained limit in bytes
+    ///
+    /// This is only produced by actix (see `MultipartConfig`),
+    /// axum limits the request body using its `DefaultBodyLimit` instead.
+    TooLarge(usize),
+}
+
+impl fmt::Display for MultipartError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MultipartError::MissingPart(name) => write!(f, "Missing part `{name}`"),
+            MultipartError::DuplicatePart(name) => write!(f, "Duplicate part `{name}`"),
+            MultipartError::InvalidText(name) => write!(f, "Part `{name}` is not valid utf-8"),
+            MultipartError::TooLarge(limit) => {
+                write!(f, "The parts exceed the limit of {limit} bytes")
+            }
+        }
+    }
+}
+
+impl Error for MultipartError {}
+
+/// Configuration for extracting a [`TypedMultipart`] in actix
+///
+/// Register it using `App::app_data` to change the default limit of 2 MiB:
+///
+/// ```rust
+/// # use actix_web::App;
+/// # use swaggapi::multipart::MultipartConfig;
+/// let app = App::new().app_data(MultipartConfig::default().limit(16 * 1024 * 1024));
+/// ```
+#[cfg(feature = "actix-multipart")]
+#[derive(Copy, Clone, Debug)]
+pub struct MultipartConfig {
+    limit: usize,
+}
+
+#[cfg(feature = "actix-multipart")]
+impl MultipartConfig {
+    /// Sets the maximum number of bytes all parts' contents may have together
+    pub const fn limit(mut self, limit: usize) -> Self {
+        self.limit = limit;
+        self
+    }
+}
+
+#[cfg(feature = "actix-multipart")]
+impl Default for MultipartConfig {
+    fn default() -> Self {
+        Self {
+            limit: 2 * 1024 * 1024,
+        }
+    }
+}
+
+impl MultipartField for FileUpload {
+    fn schema(_gen: &mut SchemaGenerator) -> ReferenceOr<Schema> {
+        ReferenceOr::Item(Schema {
+            schema_data: Default::default(),
+            schema_kind: SchemaKind::Type(Type::String(StringType {
+                format: VariantOrUnknownOrEmpty::Item(StringFormat::Binary),
+                ..Default::default()
+            })),
+        })
+    }
+
+    fn content_type() -> &'static str {
+        "application/octet-stream"
+    }
+
+    fn from_parts(name: &'static str, mut parts: Vec<FileUpload>) -> Result<Self, MultipartError> {
+        match parts.len() {
+            0 => Err(MultipartError::MissingPart(name)),
+            1 => Ok(parts.remove(0)),
+            _ => Err(MultipartError::DuplicatePart(name)),
+        }
+    }
+}
+
+impl MultipartField for String {
+    fn schema(gen: &mut SchemaGenerator) -> ReferenceOr<Schema> {
+        gen.generate::<String>()
+    }
+
+    fn content_type() -> &'static str {
+        "text/plain"
+    }
+
+    fn from_parts(name: &'static str, parts: Vec<FileUpload>) -> Result<Self, MultipartError> {
+        let part = FileUpload::from_parts(name, parts)?;
+        String::from_utf8(part.data.into()).map_err(|_| MultipartError::InvalidText(name))
+    }
+}
+
+impl<F: MultipartField> MultipartField for Option<F> {
+    fn schema(gen: &mut SchemaGenerator) -> ReferenceOr<Schema> {
+        F::schema(gen)
+    }
+
+    fn required() -> bool {
+        false
+    }
+
+    fn content_type() -> &'static str {
+        F::content_type()
+    }
+
+    fn from_parts(name: &'static str, parts: Vec<FileUpload>) -> Result<Self, MultipartError> {
+        if parts.is_empty() {
+            Ok(None)
+        } else {
+            F::from_parts(name, parts).map(Some)
+        }
+    }
+}
+
+impl<F: MultipartField> MultipartField for Vec<F> {
+    fn schema(gen: &mut SchemaGenerator) -> ReferenceOr<Schema> {
+        ReferenceOr::Item(Schema {
+            schema_data: Default::default(),
+            schema_kind: SchemaKind::Type(Type::Array(ArrayType {
+                items: Some(match F::schema(gen) {
+                    ReferenceOr::Item(item) => ReferenceOr::Item(Box::new(item)),
+                    ReferenceOr::Reference { reference } => ReferenceOr::Reference { reference },
+                }),
+                min_items: None,
+                max_items: None,
+                unique_items: false,
+            })),
+        })
+    }
+
+    fn required() -> bool {
+        false
+    }
+
+    fn content_type() -> &'static str {
+        F::content_type()
+    }
+
+    fn from_parts(name: &'static str, parts: Vec<FileUpload>) -> Result<Self, MultipartError> {
+        parts
+            .into_iter()
+            .map(|part| F::from_parts(name, vec![part]))
+            .collect()
+    }
+}
+
+#[cfg(feature = "actix-multipart")]
+const _: () = {
+    use std::future::Future;
+    use std::pin::Pin;
+
+    use actix_web::dev::Payload;
+    use actix_web::web;
+    use actix_web::FromRequest;
+    use actix_web::HttpRequest;
+    use actix_web::ResponseError;
+    use bytes::BytesMut;
+    use futures_util::StreamExt;
+
+    impl ResponseError for MultipartError {
+        fn status_code(&self) -> actix_web::http::StatusCode {
+            match self {
+                MultipartError::TooLarge(_) => actix_web::http::StatusCode::PAYLOAD_TOO_LARGE,
+                _ => actix_web::http::StatusCode::BAD_REQUEST,
+            }
+        }
+    }
+
+    impl<T: FromMultipart + 'static> FromRequest for TypedMultipart<T> {
+        type Error = actix_web::Error;
+        type Future = Pin<Box<dyn Future<Output = Result<Self, Self::Error>>>>;
+
+        fn from_request(req: &HttpRequest, payload: &mut Payload) -> Self::Future {
+            let limit = req
+                .app_data::<MultipartConfig>()
+                .or_else(|| {
+                    req.app_data::<web::Data<MultipartConfig>>()
+                        .map(|data| &***data)
+                })
+                .copied()
+                .unwrap_or_default()
+                .limit;
+            let mut multipart = actix_multipart::Multipart::new(req.headers(), payload.take());
+            Box::pin(async move {
+                let mut parts = MultipartParts::default();
+                let mut size = 0;
+                while let Some(field) = multipart.next().await {
+                    let mut field = field?;
+                    let Some(name) = field.name().map(str::to_string) else {
+                        continue;
+                    };
+                    let file_name = field
+                        .content_disposition()
+                        .and_then(|disposition| disposition.get_filename())
+                        .map(str::to_string);
+                    let content_type = field.content_type().map(ToString::to_string);
+
+                    let mut data = BytesMut::new();
+                    while let Some(chunk) = field.next().await {
+                        let chunk = chunk?;
+                        size += chunk.len();
+                        if size > limit {
+                            return Err(MultipartError::TooLarge(limit).into());
+                        }
+                        data.extend_from_slice(&chunk);
+                    }
+
+                    parts.push(
+                        name,
+                        FileUpload {
+                            file_name,
+                            content_type,
+                            data: data.freeze(),
+                        },
+                    );
+                }
+                Ok(TypedMultipart(T::from_multipart(parts)?))
+            })
+        }
+    }
+};
+
+#[cfg(feature = "axum-multipart")]
+const _: () = {
+    use axum::extract::FromRequest;
+    use axum::extract::Multipart;
+    use axum::extract::Request;
+    use axum::http::StatusCode;
+    use axum::response::IntoResponse;
+    use axum::response::Response;
+
+    impl IntoResponse for MultipartError {
+        fn into_response(self) -> Response {
+            let status = match self {
+                MultipartError::TooLarge(_) => StatusCode::PAYLOAD_TOO_LARGE,
+                _ => StatusCode::BAD_REQUEST,
+            };
+            (status, self.to_string()).into_response()
+        }
+    }
+
+    impl<T: FromMultipart, S: Send + Sync> FromRequest<S> for TypedMultipart<T> {
+        type Rejection = Response;
+
+        async fn from_request(req: Request, state: &S) -> Result<Self, Self::Rejection> {
+            let mut multipart = Multipart::from_request(req, state)
+                .await
+                .map_err(IntoResponse::into_response)?;
+
+            let mut parts = MultipartParts::default();
+            while let Some(field) = multipart
+                .next_field()
+                .await
+                .map_err(IntoResponse::into_response)?
+            {
+                let Some(name) = field.name().map(str::to_string) else {
+                    continue;
+                };
+                let file_name = field.file_name().map(str::to_string);
+                let content_type = field.content_type().map(str::to_string);
+                let data = field.bytes().await.map_err(IntoResponse::into_response)?;
+
+                parts.push(
+                    name,
+                    FileUpload {
+                        file_name,
+                        content_type,
+                        data,
+                    },
+                );
+            }
+            T::from_multipart(parts)
+                .map(TypedMultipart)
+                .map_err(IntoResponse::into_response)
+        }
+    }
+};
@@ -1,12 +1,25 @@
 use core::fmt;
+use std::borrow::Cow;
 use std::sync::Arc;
 use std::sync::Mutex;
 
 use openapiv3::OpenAPI;
 use schemars::JsonSchema;
 
+use crate::diagnostics::ConflictingRequestBodies;
+use crate::diagnostics::ConversionDiagnostic;
+use crate::diagnostics::DuplicateOperationId;
+use crate::diagnostics::DuplicateRoute;
 use crate::internals::AccessSwaggapiPageBuilder;
+use crate::internals::ContextHandler;
+use crate::internals::PageHandle;
+use crate::internals::SwaggapiHandler;
 use crate::internals::SwaggapiPageBuilderImpl;
+use crate::security::SecurityRequirement;
+use crate::security::SecurityScheme;
+use crate::servers::Server;
+use crate::tags::Tag;
+use crate::tags::TagGroup;
 
 /// A page is a collection of api endpoints
 ///
@@ -30,6 +43,95 @@ pub trait SwaggapiPage: AccessSwaggapiPageBuilder {
     /// The internal build process is cached (hence the `Arc`) so feel free to call this eagerly.
     fn openapi(&self) -> Arc<OpenAPI>;
 
+    /// Returns the OpenAPI document in the version set by [`SwaggapiPageBuilder::openapi_version`]
+    ///
+    /// Unlike [`SwaggapiPage::openapi`], this is not limited to OpenAPI 3.0.
+    /// This is the document served by [`SwaggerUi`](crate::SwaggerUi)
+    /// which keeps the keys of its objects (like the paths) in the order they were added.
+    ///
+    /// The internal build process is cached (hence the `Arc`) so feel free to call this eagerly.
+    fn openapi_json(&self) -> Arc<serde_json::Value>;
+
+    /// Returns the problems encountered converting the page's schemas
+    ///
+    /// [`schemars`] generates JSON Schema which can use keywords unsupported by OpenAPI 3.0.
+    /// Those keywords are dropped from the document and reported here.
+    ///
+    /// ```rust
+    /// # use swaggapi::SwaggapiPage;
+    /// # use swaggapi::SwaggapiPageBuilder;
+    /// static PAGE: SwaggapiPageBuilder = SwaggapiPageBuilder::new();
+    ///
+    /// for diagnostic in (&PAGE).diagnostics() {
+    ///     eprintln!("{diagnostic}");
+    /// }
+    /// ```
+    fn diagnostics(&self) -> Vec<ConversionDiagnostic>;
+
+    /// Checks the page's schemas could be converted without loss
+    ///
+    /// Returns the [`SwaggapiPage::diagnostics`] as error if there are any.
+    /// Call this at startup or from a test to fail early instead of serving an incomplete document:
+    ///
+    /// ```rust
+    /// # use swaggapi::SwaggapiPage;
+    /// # use swaggapi::SwaggapiPageBuilder;
+    /// static PAGE: SwaggapiPageBuilder = SwaggapiPageBuilder::new();
+    ///
+    /// if let Err(diagnostics) = (&PAGE).validate() {
+    ///     for diagnostic in diagnostics {
+    ///         eprintln!("{diagnostic}");
+    ///     }
+    /// }
+    /// ```
+    fn validate(&self) -> Result<(), Vec<ConversionDiagnostic>>;
+
+    /// Returns the handlers which were added for an already documented http method and path
+    ///
+    /// See [`SwaggapiPageBuilder::on_duplicate_route`] for which of them ends up in the document.
+    fn duplicate_routes(&self) -> Vec<DuplicateRoute>;
+
+    /// Checks the page doesn't contain duplicate routes if they are configured to be an error
+    ///
+    /// Returns the [`SwaggapiPage::duplicate_routes`] as error
+    /// if there are any and the page uses [`DuplicateRouteBehavior::Error`].
+    fn validate_routes(&self) -> Result<(), Vec<DuplicateRoute>>;
+
+    /// Returns the handlers which have several arguments consuming the request body
+    ///
+    /// Such a handler can't extract its arguments,
+    /// check this from a test to catch handlers accidentally declaring two body extractors.
+    /// [`strict`](SwaggapiPageBuilder::strict) pages do so when their document is requested.
+    fn conflicting_request_bodies(&self) -> Vec<ConflictingRequestBodies>;
+
+    /// Returns the handlers whose derived `operationId` was already used and had to be renamed
+    ///
+    /// Explicit `operation_id`s set in [`#[handler]`](crate::handler) are only renamed
+    /// if two handlers on the page set the same one,
+    /// which makes [`SwaggapiPage::openapi`] and [`SwaggapiPage::openapi_json`] panic on [`strict`](SwaggapiPageBuilder::strict) pages.
+    fn duplicate_operation_ids(&self) -> Vec<DuplicateOperationId>;
+
+    /// Removes every handler, webhook and schema added to this page
+    ///
+    /// The page's configuration is kept.
+    fn reset(&self);
+
+    /// Captures the handlers, webhooks and schemas currently added to this page
+    ///
+    /// Use [`SwaggapiPage::restore`] to return to this state later.
+    fn snapshot(&self) -> PageSnapshot;
+
+    /// Replaces the handlers, webhooks and schemas added to this page with the ones of a [`SwaggapiPage::snapshot`]
+    fn restore(&self, snapshot: &PageSnapshot);
+
+    /// Adds a webhook to this page
+    ///
+    /// The handler describes the request the api sends to its subscribers
+    /// and the responses it expects, its path is ignored.
+    ///
+    /// Webhooks are only included in OpenAPI 3.1 documents.
+    fn add_webhook(&self, name: &str, handler: SwaggapiHandler) -> &Self;
+
     /// Explicitly adds a schema to this page
     ///
     /// This method's use cases are rare,
@@ -38,22 +140,123 @@ pub trait SwaggapiPage: AccessSwaggapiPageBuilder {
 }
 impl<P: AccessSwaggapiPageBuilder> SwaggapiPage for P {
     fn openapi(&self) -> Arc<OpenAPI> {
-        SwaggapiPageBuilderImpl::build(self.get_builder())
+        let builder = self.get_builder();
+        SwaggapiPageBuilderImpl::assert_valid(&builder);
+        SwaggapiPageBuilderImpl::build(&builder)
+    }
+
+    fn openapi_json(&self) -> Arc<serde_json::Value> {
+        let builder = self.get_builder();
+        SwaggapiPageBuilderImpl::assert_valid(&builder);
+        SwaggapiPageBuilderImpl::build_json(&builder)
+    }
+
+    fn diagnostics(&self) -> Vec<ConversionDiagnostic> {
+        SwaggapiPageBuilderImpl::diagnostics(&self.get_builder())
+    }
+
+    fn validate(&self) -> Result<(), Vec<ConversionDiagnostic>> {
+        SwaggapiPageBuilderImpl::validate(&self.get_builder())
+    }
+
+    fn duplicate_routes(&self) -> Vec<DuplicateRoute> {
+        SwaggapiPageBuilderImpl::duplicate_routes(&self.get_builder())
+    }
+
+    fn validate_routes(&self) -> Result<(), Vec<DuplicateRoute>> {
+        SwaggapiPageBuilderImpl::validate_routes(&self.get_builder())
+    }
+
+    fn conflicting_request_bodies(&self) -> Vec<ConflictingRequestBodies> {
+        SwaggapiPageBuilderImpl::conflicting_request_bodies(&self.get_builder())
+    }
+
+    fn duplicate_operation_ids(&self) -> Vec<DuplicateOperationId> {
+        SwaggapiPageBuilderImpl::duplicate_operation_ids(&self.get_builder())
+    }
+
+    fn reset(&self) {
+        *self.get_builder().state.lock().unwrap() = None;
+    }
+
+    fn snapshot(&self) -> PageSnapshot {
+        PageSnapshot(self.get_builder().state.lock().unwrap().clone())
+    }
+
+    fn restore(&self, snapshot: &PageSnapshot) {
+        *self.get_builder().state.lock().unwrap() = snapshot.0.clone();
+    }
+
+    fn add_webhook(&self, name: &str, handler: SwaggapiHandler) -> &Self {
+        SwaggapiPageBuilderImpl::add_webhook(
+            &self.get_builder(),
+            name,
+            &ContextHandler::new(handler),
+        );
+        self
     }
 
     fn add_schema<T: JsonSchema>(&self) -> &Self {
-        SwaggapiPageBuilderImpl::add_schema::<T>(self.get_builder());
+        SwaggapiPageBuilderImpl::add_schema::<T>(&self.get_builder());
         self
     }
 }
 
+/// The handlers, webhooks and schemas added to a page at some point
+///
+/// See [`SwaggapiPage::snapshot`].
+#[derive(Clone)]
+pub struct PageSnapshot(Option<SwaggapiPageBuilderImpl>);
+
+impl fmt::Debug for PageSnapshot {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PageSnapshot").finish_non_exhaustive()
+    }
+}
+
 /// An implicit [`SwaggapiPage`] which will always contain your entire api
+///
+/// Contexts using an isolated [`Registry`](crate::Registry) add their handlers to the registry's copy instead.
 pub struct PageOfEverything;
 /// "Manual expansion" of [`derive(SwaggapiPage)`](crate::SwaggapiPage)
 impl AccessSwaggapiPageBuilder for PageOfEverything {
-    fn get_builder(&self) -> &'static SwaggapiPageBuilder {
+    fn get_builder(&self) -> PageHandle {
         static BUILDER: SwaggapiPageBuilder = SwaggapiPageBuilder::new();
-        &BUILDER
+        PageHandle::Static(&BUILDER)
+    }
+}
+
+/// A [`SwaggapiPage`] created at runtime
+///
+/// Unlike a `static` [`SwaggapiPageBuilder`] or a [`#[derive(SwaggapiPage)]`](macro@crate::SwaggapiPage) struct,
+/// its metadata may be computed at runtime
+/// and several independent instances can be created (for example one per test).
+///
+/// Cloning is cheap and the clones refer to the same page.
+///
+/// ```rust
+/// # use swaggapi::RuntimePage;
+/// # use swaggapi::SwaggapiPageBuilder;
+/// # let environment = "staging";
+/// let page = RuntimePage::new(
+///     SwaggapiPageBuilder::new()
+///         .set_title(format!("My application ({environment})"))
+///         .version(env!("CARGO_PKG_VERSION")),
+/// );
+///
+/// // use page.clone() wherever an `impl SwaggapiPage` is required
+/// ```
+#[derive(Clone, Debug)]
+pub struct RuntimePage(Arc<SwaggapiPageBuilder>);
+impl RuntimePage {
+    /// Creates a new page from a builder
+    pub fn new(builder: SwaggapiPageBuilder) -> Self {
+        Self(Arc::new(builder))
+    }
+}
+impl AccessSwaggapiPageBuilder for RuntimePage {
+    fn get_builder(&self) -> PageHandle {
+        PageHandle::Shared(self.0.clone())
     }
 }
 
@@ -72,17 +275,35 @@ impl AccessSwaggapiPageBuilder for PageOfEverything {
 ///
 /// This example is semantically equivalent to the one from [`#[derive(SwaggapiPage)]`](macro@crate::SwaggapiPage).
 pub struct SwaggapiPageBuilder {
-    pub(crate) title: Option<&'static str>,
-    pub(crate) description: Option<&'static str>,
-    pub(crate) terms_of_service: Option<&'static str>,
-    pub(crate) contact_name: Option<&'static str>,
-    pub(crate) contact_url: Option<&'static str>,
-    pub(crate) contact_email: Option<&'static str>,
-    pub(crate) license_name: Option<&'static str>,
-    pub(crate) license_url: Option<&'static str>,
-    pub(crate) version: Option<&'static str>,
-
-    pub(crate) filename: Option<&'static str>,
+    pub(crate) title: PageString,
+    pub(crate) description: PageString,
+    pub(crate) terms_of_service: PageString,
+    pub(crate) contact_name: PageString,
+    pub(crate) contact_url: PageString,
+    pub(crate) contact_email: PageString,
+    pub(crate) license_name: PageString,
+    pub(crate) license_url: PageString,
+    pub(crate) version: PageString,
+
+    pub(crate) filename: PageString,
+
+    pub(crate) servers: &'static [Server],
+
+    pub(crate) security_schemes: &'static [(&'static str, SecurityScheme)],
+    pub(crate) security: &'static [SecurityRequirement],
+
+    pub(crate) tags: &'static [Tag],
+    pub(crate) tag_groups: &'static [TagGroup],
+
+    pub(crate) rejection_responses: bool,
+
+    pub(crate) openapi_version: OpenApiVersion,
+
+    pub(crate) operation_ids: OperationIdStrategy,
+
+    pub(crate) on_duplicate_route: DuplicateRouteBehavior,
+
+    pub(crate) strict: bool,
 
     pub(crate) state: Mutex<Option<SwaggapiPageBuilderImpl>>,
 }
@@ -95,62 +316,122 @@ impl fmt::Debug for SwaggapiPageBuilder {
 }
 
 impl SwaggapiPageBuilder {
+    /// Creates a new builder with the same configuration but without any handlers
+    pub(crate) fn copy_config(&self) -> Self {
+        let Self {
+            title,
+            description,
+            terms_of_service,
+            contact_name,
+            contact_url,
+            contact_email,
+            license_name,
+            license_url,
+            version,
+            filename,
+            servers,
+            security_schemes,
+            security,
+            tags,
+            tag_groups,
+            rejection_responses,
+            openapi_version,
+            operation_ids,
+            on_duplicate_route,
+            strict,
+            state: _,
+        } = self;
+        Self {
+            title: title.clone(),
+            description: description.clone(),
+            terms_of_service: terms_of_service.clone(),
+            contact_name: contact_name.clone(),
+            contact_url: contact_url.clone(),
+            contact_email: contact_email.clone(),
+            license_name: license_name.clone(),
+            license_url: license_url.clone(),
+            version: version.clone(),
+            filename: filename.clone(),
+            servers,
+            security_schemes,
+            security,
+            tags,
+            tag_groups,
+            rejection_responses: *rejection_responses,
+            openapi_version: *openapi_version,
+            operation_ids: *operation_ids,
+            on_duplicate_route: *on_duplicate_route,
+            strict: *strict,
+            state: Mutex::new(None),
+        }
+    }
+
     /// Construct a new empty builder
     pub const fn new() -> Self {
         Self {
-            title: None,
-            description: None,
-            terms_of_service: None,
-            contact_name: None,
-            contact_url: None,
-            contact_email: None,
-            license_name: None,
-            license_url: None,
-            version: None,
-            filename: None,
+            title: PageString::new(),
+            description: PageString::new(),
+            terms_of_service: PageString::new(),
+            contact_name: PageString::new(),
+            contact_url: PageString::new(),
+            contact_email: PageString::new(),
+            license_name: PageString::new(),
+            license_url: PageString::new(),
+            version: PageString::new(),
+            filename: PageString::new(),
+            servers: &[],
+            security_schemes: &[],
+            security: &[],
+            tags: &[],
+            tag_groups: &[],
+            rejection_responses: false,
+            openapi_version: OpenApiVersion::V3_0,
+            operation_ids: OperationIdStrategy::Ident,
+            on_duplicate_route: DuplicateRouteBehavior::LastWins,
+            strict: false,
             state: Mutex::new(None),
         }
     }
 
     /// The title of the application.
     pub const fn title(mut self, title: &'static str) -> Self {
-        self.title = Some(title);
+        self.title.set_const(title);
         self
     }
 
     /// A short description of the application.
     pub const fn description(mut self, description: &'static str) -> Self {
-        self.description = Some(description);
+        self.description.set_const(description);
         self
     }
 
     /// A URL to the Terms of Service for the API.
     pub const fn terms_of_service(mut self, terms: &'static str) -> Self {
-        self.terms_of_service = Some(terms);
+        self.terms_of_service.set_const(terms);
         self
     }
 
     /// The identifying name of the contact person/organization for the exposed API.
     pub const fn contact_name(mut self, name: &'static str) -> Self {
-        self.contact_name = Some(name);
+        self.contact_name.set_const(name);
         self
     }
 
     /// The URL pointing to the contact information for the exposed API.
     pub const fn contact_url(mut self, url: &'static str) -> Self {
-        self.contact_url = Some(url);
+        self.contact_url.set_const(url);
         self
     }
 
     /// The email address of the contact person/organization for the exposed API.
     pub const fn contact_email(mut self, email: &'static str) -> Self {
-        self.contact_email = Some(email);
+        self.contact_email.set_const(email);
         self
     }
 
     /// The license name used for the API.
     pub const fn license_name(mut self, name: &'static str) -> Self {
-        self.license_name = Some(name);
+        self.license_name.set_const(name);
         self
     }
 
@@ -158,13 +439,336 @@ impl SwaggapiPageBuilder {
     ///
     /// You should also set the `license_name`.
     pub const fn license_url(mut self, url: &'static str) -> Self {
-        self.license_url = Some(url);
+        self.license_url.set_const(url);
+        self
+    }
+
+    /// The version of the api
+    ///
+    /// Defaults to `"v0.0.0"`.
+    pub const fn version(mut self, version: &'static str) -> Self {
+        self.version.set_const(version);
         self
     }
 
     /// The filename the page will be served as
     pub const fn filename(mut self, file: &'static str) -> Self {
-        self.filename = Some(file);
+        self.filename.set_const(file);
+        self
+    }
+
+    /// The servers the api is available on
+    ///
+    /// See [`Server`] for an example.
+    pub const fn servers(mut self, servers: &'static [Server]) -> Self {
+        self.servers = servers;
+        self
+    }
+
+    /// The security schemes available to the page's handlers
+    ///
+    /// Each scheme is registered under a name
+    /// which is used to reference it in [`SecurityRequirement`]s.
+    ///
+    /// ```rust
+    /// # use swaggapi::SwaggapiPageBuilder;
+    /// # use swaggapi::security::SecurityScheme;
+    /// static PAGE: SwaggapiPageBuilder = SwaggapiPageBuilder::new()
+    ///     .security_schemes(&[
+    ///         ("bearer", SecurityScheme::bearer().bearer_format("JWT")),
+    ///         ("api_key", SecurityScheme::api_key_header("X-Api-Key")),
+    ///     ]);
+    /// ```
+    pub const fn security_schemes(
+        mut self,
+        schemes: &'static [(&'static str, SecurityScheme)],
+    ) -> Self {
+        self.security_schemes = schemes;
+        self
+    }
+
+    /// The security requirements applied to every handler which doesn't specify its own
+    pub const fn security(mut self, requirements: &'static [SecurityRequirement]) -> Self {
+        self.security = requirements;
+        self
+    }
+
+    /// The definitions of the tags used by the page's handlers
+    ///
+    /// The tags are listed in this order, followed by the ones defined using [`ApiContext::tag_definition`](crate::ApiContext::tag_definition).
+    /// See [`Tag`] for an example.
+    pub const fn tags(mut self, tags: &'static [Tag]) -> Self {
+        self.tags = tags;
         self
     }
+
+    /// Groups of tags emitted as `x-tagGroups` extension
+    ///
+    /// See [`TagGroup`] for details.
+    pub const fn tag_groups(mut self, groups: &'static [TagGroup]) -> Self {
+        self.tag_groups = groups;
+        self
+    }
+
+    /// Should the responses sent when a handler's arguments reject a request be documented?
+    ///
+    /// For example a handler taking `Json<T>` would get `400`, `415` and `422` responses.
+    /// See [`HandlerArgument::rejection_responses`](crate::handler_argument::HandlerArgument::rejection_responses).
+    ///
+    /// Defaults to `false`, so upgrading doesn't add responses to existing documents.
+    pub const fn rejection_responses(mut self, enabled: bool) -> Self {
+        self.rejection_responses = enabled;
+        self
+    }
+
+    /// The version of the OpenAPI specification the page is served as
+    ///
+    /// Defaults to [`OpenApiVersion::V3_0`].
+    /// See [`OpenApiVersion`] for the differences.
+    pub const fn openapi_version(mut self, version: OpenApiVersion) -> Self {
+        self.openapi_version = version;
+        self
+    }
+
+    /// How the `operationId`s of handlers without an explicit `operation_id` are derived
+    ///
+    /// Defaults to [`OperationIdStrategy::Ident`].
+    ///
+    /// Regardless of the strategy, duplicate derived `operationId`s are disambiguated
+    /// by appending a number when the page is built (see [`SwaggapiPage::duplicate_operation_ids`]).
+    pub const fn operation_ids(mut self, strategy: OperationIdStrategy) -> Self {
+        self.operation_ids = strategy;
+        self
+    }
+
+    /// What should happen if a handler is added for an http method and path which is already documented?
+    ///
+    /// Defaults to [`DuplicateRouteBehavior::LastWins`].
+    /// The collisions can be inspected using [`SwaggapiPage::duplicate_routes`].
+    pub const fn on_duplicate_route(mut self, behavior: DuplicateRouteBehavior) -> Self {
+        self.on_duplicate_route = behavior;
+        self
+    }
+
+    /// Should [`SwaggapiPage::openapi`] and [`SwaggapiPage::openapi_json`] panic if the page's schemas couldn't be converted without loss?
+    ///
+    /// This is an opt-in intended for tests which should catch
+    /// any of the [`SwaggapiPage::diagnostics`] being introduced.
+    /// The [`SwaggerUi`](crate::SwaggerUi) never panics and serves the document regardless,
+    /// use [`SwaggapiPage::validate`] to handle the diagnostics without panicking.
+    ///
+    /// Handlers with several arguments consuming the request body (see [`SwaggapiPage::conflicting_request_bodies`])
+    /// and handlers setting the same explicit `operation_id` (see [`SwaggapiPage::duplicate_operation_ids`])
+    /// are an error as well.
+    ///
+    /// Defaults to `false`.
+    pub const fn strict(mut self, enabled: bool) -> Self {
+        self.strict = enabled;
+        self
+    }
+}
+
+/// Setters accepting strings created at runtime
+///
+/// They can't be used to construct a `static` builder.
+/// Use them together with a [`RuntimePage`] instead.
+impl SwaggapiPageBuilder {
+    /// Like [`SwaggapiPageBuilder::title`] but accepts a string created at runtime
+    pub fn set_title(mut self, title: impl Into<Cow<'static, str>>) -> Self {
+        self.title.set(title);
+        self
+    }
+
+    /// Like [`SwaggapiPageBuilder::description`] but accepts a string created at runtime
+    pub fn set_description(mut self, description: impl Into<Cow<'static, str>>) -> Self {
+        self.description.set(description);
+        self
+    }
+
+    /// Like [`SwaggapiPageBuilder::terms_of_service`] but accepts a string created at runtime
+    pub fn set_terms_of_service(mut self, terms: impl Into<Cow<'static, str>>) -> Self {
+        self.terms_of_service.set(terms);
+        self
+    }
+
+    /// Like [`SwaggapiPageBuilder::contact_name`] but accepts a string created at runtime
+    pub fn set_contact_name(mut self, name: impl Into<Cow<'static, str>>) -> Self {
+        self.contact_name.set(name);
+        self
+    }
+
+    /// Like [`SwaggapiPageBuilder::contact_url`] but accepts a string created at runtime
+    pub fn set_contact_url(mut self, url: impl Into<Cow<'static, str>>) -> Self {
+        self.contact_url.set(url);
+        self
+    }
+
+    /// Like [`SwaggapiPageBuilder::contact_email`] but accepts a string created at runtime
+    pub fn set_contact_email(mut self, email: impl Into<Cow<'static, str>>) -> Self {
+        self.contact_email.set(email);
+        self
+    }
+
+    /// Like [`SwaggapiPageBuilder::license_name`] but accepts a string created at runtime
+    pub fn set_license_name(mut self, name: impl Into<Cow<'static, str>>) -> Self {
+        self.license_name.set(name);
+        self
+    }
+
+    /// Like [`SwaggapiPageBuilder::license_url`] but accepts a string created at runtime
+    pub fn set_license_url(mut self, url: impl Into<Cow<'static, str>>) -> Self {
+        self.license_url.set(url);
+        self
+    }
+
+    /// Like [`SwaggapiPageBuilder::version`] but accepts a string created at runtime
+    pub fn set_version(mut self, version: impl Into<Cow<'static, str>>) -> Self {
+        self.version.set(version);
+        self
+    }
+
+    /// Like [`SwaggapiPageBuilder::filename`] but accepts a string created at runtime
+    pub fn set_filename(mut self, file: impl Into<Cow<'static, str>>) -> Self {
+        self.filename.set(file);
+        self
+    }
+}
+
+/// A string of a [`SwaggapiPageBuilder`] which can be set both in a `const` context and at runtime
+///
+/// A `const fn` can't drop a string set at runtime,
+/// so the `const` setters store their `&'static str` next to it where it takes precedence.
+/// The `set_*` methods clear it again.
+#[derive(Clone)]
+pub(crate) struct PageString {
+    /// Set by the `const` setters
+    constant: Option<&'static str>,
+
+    /// Set by the `set_*` methods
+    runtime: Option<Cow<'static, str>>,
+}
+
+impl PageString {
+    const fn new() -> Self {
+        Self {
+            constant: None,
+            runtime: None,
+        }
+    }
+
+    const fn set_const(&mut self, value: &'static str) {
+        self.constant = Some(value);
+    }
+
+    fn set(&mut self, value: impl Into<Cow<'static, str>>) {
+        self.constant = None;
+        self.runtime = Some(value.into());
+    }
+
+    /// Get the string set last
+    pub(crate) fn get(&self) -> Option<&str> {
+        self.constant.or(self.runtime.as_deref())
+    }
+
+    /// Get the string set last without borrowing the builder
+    pub(crate) fn to_cow(&self) -> Option<Cow<'static, str>> {
+        self.constant
+            .map(Cow::Borrowed)
+            .or_else(|| self.runtime.clone())
+    }
+}
+
+/// A version of the OpenAPI specification a page can be served as
+///
+/// [`SwaggapiPage::openapi`] always returns OpenAPI 3.0
+/// while [`SwaggapiPage::openapi_json`] and the [`SwaggerUi`](crate::SwaggerUi) respect this version.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug, Default)]
+pub enum OpenApiVersion {
+    /// OpenAPI 3.0
+    ///
+    /// The schemas generated by [`schemars`] are converted into OpenAPI's subset of JSON Schema
+    /// which loses some keywords like `if` or `patternProperties`.
+    #[default]
+    V3_0,
+
+    /// OpenAPI 3.1
+    ///
+    /// The schemas generated by [`schemars`] are embedded as they are,
+    /// `nullable` is replaced with the `null` type
+    /// and webhooks added through [`SwaggapiPage::add_webhook`] are included.
+    V3_1,
+}
+
+/// How a page derives the `operationId` of a handler
+///
+/// A handler's `operation_id` set in [`#[handler]`](crate::handler) always takes precedence.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug, Default)]
+pub enum OperationIdStrategy {
+    /// Use the handler function's identifier, for example `get_user`
+    #[default]
+    Ident,
+
+    /// Prefix the identifier with the path of the module the handler is declared in
+    ///
+    /// The crate's name is omitted and the segments are joined with `_`,
+    /// for example `api_users_get` for the handler `get` in `my_crate::api::users`.
+    ModulePath,
+
+    /// Prefix the identifier with the handler's tag, for example `users_get`
+    ///
+    /// If the handler has several tags (including the ones added by [`ApiContext::tag`](crate::ApiContext::tag)),
+    /// the alphabetically first one is used.
+    /// Handlers without any tag fall back to [`OperationIdStrategy::Ident`].
+    Tag,
+}
+
+/// How a page handles several handlers sharing the same http method and path
+///
+/// See [`SwaggapiPageBuilder::on_duplicate_route`].
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug, Default)]
+pub enum DuplicateRouteBehavior {
+    /// The duplicates are an error
+    ///
+    /// [`SwaggapiPage::validate_routes`] returns them
+    /// and [`SwaggapiPage::openapi`] and [`SwaggapiPage::openapi_json`] panic listing them.
+    /// The [`SwaggerUi`](crate::SwaggerUi) never panics and documents the handler added last.
+    Error,
+
+    /// The handler added first is documented
+    FirstWins,
+
+    /// The handler added last is documented
+    #[default]
+    LastWins,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn the_string_set_last_is_used() {
+        let builder = SwaggapiPageBuilder::new().title("first").title("second");
+        assert_eq!(builder.title.get(), Some("second"));
+
+        let builder = SwaggapiPageBuilder::new()
+            .set_title(String::from("runtime"))
+            .title("const");
+        assert_eq!(builder.title.get(), Some("const"));
+
+        let builder = SwaggapiPageBuilder::new()
+            .title("const")
+            .set_title(String::from("runtime"));
+        assert_eq!(builder.title.get(), Some("runtime"));
+
+        let builder = SwaggapiPageBuilder::new()
+            .set_title(String::from("first"))
+            .title("const")
+            .set_title(String::from("second"));
+        assert_eq!(builder.title.get(), Some("second"));
+        assert_eq!(
+            builder.copy_config().title.to_cow().as_deref(),
+            Some("second")
+        );
+    }
 }
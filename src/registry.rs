@@ -0,0 +1,77 @@
+use std::sync::Arc;
+use std::sync::Mutex;
+
+use crate::internals::AccessSwaggapiPageBuilder;
+use crate::internals::PageHandle;
+use crate::page::RuntimePage;
+use crate::PageOfEverything;
+use crate::SwaggapiPage;
+
+/// The place an [`ApiContext`](crate::ApiContext) adds its handlers to
+///
+/// By default, handlers are added to the pages themselves.
+/// Since pages are usually `static`, their handlers are shared by the entire process.
+/// This is a problem when several applications are created in one process,
+/// for example by tests which run in parallel.
+///
+/// An [isolated](Registry::isolated) registry stores its own copy of every page it is used with.
+/// Use it with [`ApiContext::registry`](crate::ApiContext::registry) and [`SwaggerUi::registry`](crate::SwaggerUi::registry)
+/// and access its copies through [`Registry::page`]:
+///
+/// ```rust
+/// # use swaggapi::{PageOfEverything, Registry, SwaggapiPage};
+/// let registry = Registry::isolated();
+///
+/// // ApiContext::new().registry(registry.clone()).handler(...)
+///
+/// let openapi = registry.page(PageOfEverything).openapi();
+/// assert!(openapi.paths.paths.is_empty());
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct Registry {
+    /// Pairs of the original page and its copy
+    ///
+    /// `None` for the global registry which doesn't copy its pages
+    copies: Option<Arc<Mutex<Copies>>>,
+}
+
+/// Pairs of an original page and its copy
+type Copies = Vec<(PageHandle, RuntimePage)>;
+
+impl Registry {
+    /// The default registry which adds handlers to the pages themselves
+    pub fn global() -> Self {
+        Self { copies: None }
+    }
+
+    /// Creates a new registry which adds handlers to its own copies of the pages
+    pub fn isolated() -> Self {
+        Self {
+            copies: Some(Default::default()),
+        }
+    }
+
+    /// Get the page which stores the handlers this registry added to `page`
+    ///
+    /// For an isolated registry, this is a copy of `page` which has the same configuration
+    /// but starts without any handlers.
+    pub fn page(&self, page: impl SwaggapiPage) -> PageHandle {
+        let page = page.get_builder();
+        let Some(copies) = &self.copies else {
+            return page;
+        };
+
+        let mut copies = copies.lock().unwrap();
+        if let Some((_, copy)) = copies.iter().find(|(original, _)| *original == page) {
+            return copy.get_builder();
+        }
+        let copy = RuntimePage::new(page.copy_config());
+        copies.push((page, copy.clone()));
+        copy.get_builder()
+    }
+
+    /// Get the registry's [`PageOfEverything`]
+    pub fn everything(&self) -> PageHandle {
+        self.page(PageOfEverything)
+    }
+}
@@ -0,0 +1,341 @@
+//! Types describing how an api is secured
+//!
+//! Security schemes are declared on a [`SwaggapiPageBuilder`](crate::SwaggapiPageBuilder)
+//! and referenced by their name from [`SecurityRequirement`]s
+//! which are attached to handlers (`#[get("/x", security("bearer"))]`)
+//! or [`ApiContext`](crate::ApiContext)s.
+
+use indexmap::IndexMap;
+use openapiv3::APIKeyLocation;
+use openapiv3::AuthorizationCodeOAuth2Flow;
+use openapiv3::ClientCredentialsOAuth2Flow;
+use openapiv3::ImplicitOAuth2Flow;
+use openapiv3::PasswordOAuth2Flow;
+
+/// A [security scheme object](https://spec.openapis.org/oas/v3.0.3#security-scheme-object)
+///
+/// Unlike [`openapiv3::SecurityScheme`] this type can be constructed in a `const` context
+/// which is required to use it in a [`SwaggapiPageBuilder`](crate::SwaggapiPageBuilder).
+#[allow(clippy::large_enum_variant)]
+#[derive(Copy, Clone, Debug)]
+pub enum SecurityScheme {
+    /// An api key passed as header, query parameter or cookie
+    ApiKey {
+        /// The location of the api key
+        location: ApiKeyLocation,
+
+        /// The name of the header, query or cookie parameter to be used
+        name: &'static str,
+
+        /// A short description for security scheme
+        description: Option<&'static str>,
+    },
+
+    /// An HTTP authentication scheme using the `Authorization` header
+    Http {
+        /// The name of the HTTP Authorization scheme (for example `"basic"` or `"bearer"`)
+        scheme: &'static str,
+
+        /// A hint to the client to identify how the bearer token is formatted (for example `"JWT"`)
+        bearer_format: Option<&'static str>,
+
+        /// A short description for security scheme
+        description: Option<&'static str>,
+    },
+
+    /// OAuth2 using one or more of its flows
+    OAuth2 {
+        /// The supported flows
+        flows: OAuth2Flows,
+
+        /// A short description for security scheme
+        description: Option<&'static str>,
+    },
+
+    /// OpenID Connect Discovery
+    OpenIdConnect {
+        /// OpenId Connect URL to discover OAuth2 configuration values
+        url: &'static str,
+
+        /// A short description for security scheme
+        description: Option<&'static str>,
+    },
+}
+
+impl SecurityScheme {
+    /// HTTP bearer authentication (`Authorization: Bearer <token>`)
+    pub const fn bearer() -> Self {
+        Self::Http {
+            scheme: "bearer",
+            bearer_format: None,
+            description: None,
+        }
+    }
+
+    /// HTTP basic authentication (`Authorization: Basic <credentials>`)
+    pub const fn basic() -> Self {
+        Self::Http {
+            scheme: "basic",
+            bearer_format: None,
+            description: None,
+        }
+    }
+
+    /// An api key passed in a header called `name`
+    pub const fn api_key_header(name: &'static str) -> Self {
+        Self::ApiKey {
+            location: ApiKeyLocation::Header,
+            name,
+            description: None,
+        }
+    }
+
+    /// An api key passed in a query parameter called `name`
+    pub const fn api_key_query(name: &'static str) -> Self {
+        Self::ApiKey {
+            location: ApiKeyLocation::Query,
+            name,
+            description: None,
+        }
+    }
+
+    /// An api key passed in a cookie called `name`
+    pub const fn api_key_cookie(name: &'static str) -> Self {
+        Self::ApiKey {
+            location: ApiKeyLocation::Cookie,
+            name,
+            description: None,
+        }
+    }
+
+    /// OAuth2 using the given flows
+    pub const fn oauth2(flows: OAuth2Flows) -> Self {
+        Self::OAuth2 {
+            flows,
+            description: None,
+        }
+    }
+
+    /// OpenID Connect Discovery using the given URL
+    pub const fn open_id_connect(url: &'static str) -> Self {
+        Self::OpenIdConnect {
+            url,
+            description: None,
+        }
+    }
+
+    /// Sets the bearer format
+    ///
+    /// This has no effect on schemes other than [`SecurityScheme::Http`].
+    pub const fn bearer_format(self, format: &'static str) -> Self {
+        match self {
+            Self::Http {
+                scheme,
+                bearer_format: _,
+                description,
+            } => Self::Http {
+                scheme,
+                bearer_format: Some(format),
+                description,
+            },
+            _ => self,
+        }
+    }
+
+    /// Sets the description
+    pub const fn description(self, text: &'static str) -> Self {
+        let description = Some(text);
+        match self {
+            Self::ApiKey { location, name, .. } => Self::ApiKey {
+                location,
+                name,
+                description,
+            },
+            Self::Http {
+                scheme,
+                bearer_format,
+                ..
+            } => Self::Http {
+                scheme,
+                bearer_format,
+                description,
+            },
+            Self::OAuth2 { flows, .. } => Self::OAuth2 { flows, description },
+            Self::OpenIdConnect { url, .. } => Self::OpenIdConnect { url, description },
+        }
+    }
+
+    /// Convert into the [`openapiv3`] representation
+    pub fn to_openapi(&self) -> openapiv3::SecurityScheme {
+        match *self {
+            Self::ApiKey {
+                location,
+                name,
+                description,
+            } => openapiv3::SecurityScheme::APIKey {
+                location: match location {
+                    ApiKeyLocation::Query => APIKeyLocation::Query,
+                    ApiKeyLocation::Header => APIKeyLocation::Header,
+                    ApiKeyLocation::Cookie => APIKeyLocation::Cookie,
+                },
+                name: name.to_string(),
+                description: description.map(str::to_string),
+                extensions: Default::default(),
+            },
+            Self::Http {
+                scheme,
+                bearer_format,
+                description,
+            } => openapiv3::SecurityScheme::HTTP {
+                scheme: scheme.to_string(),
+                bearer_format: bearer_format.map(str::to_string),
+                description: description.map(str::to_string),
+                extensions: Default::default(),
+            },
+            Self::OAuth2 { flows, description } => openapiv3::SecurityScheme::OAuth2 {
+                flows: flows.to_openapi(),
+                description: description.map(str::to_string),
+                extensions: Default::default(),
+            },
+            Self::OpenIdConnect { url, description } => openapiv3::SecurityScheme::OpenIDConnect {
+                open_id_connect_url: url.to_string(),
+                description: description.map(str::to_string),
+                extensions: Default::default(),
+            },
+        }
+    }
+}
+
+/// The location of an api key
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub enum ApiKeyLocation {
+    /// A query parameter
+    Query,
+    /// A header
+    Header,
+    /// A cookie
+    Cookie,
+}
+
+/// The OAuth2 flows supported by a [`SecurityScheme::OAuth2`]
+#[derive(Copy, Clone, Debug, Default)]
+pub struct OAuth2Flows {
+    /// Configuration for the OAuth Implicit flow
+    ///
+    /// Its `token_url` is ignored.
+    pub implicit: Option<OAuth2Flow>,
+
+    /// Configuration for the OAuth Resource Owner Password flow
+    ///
+    /// Its `authorization_url` is ignored.
+    pub password: Option<OAuth2Flow>,
+
+    /// Configuration for the OAuth Client Credentials flow
+    ///
+    /// Its `authorization_url` is ignored.
+    pub client_credentials: Option<OAuth2Flow>,
+
+    /// Configuration for the OAuth Authorization Code flow
+    pub authorization_code: Option<OAuth2Flow>,
+}
+
+impl OAuth2Flows {
+    /// Convert into the [`openapiv3`] representation
+    pub fn to_openapi(&self) -> openapiv3::OAuth2Flows {
+        openapiv3::OAuth2Flows {
+            implicit: self.implicit.map(|flow| ImplicitOAuth2Flow {
+                authorization_url: flow.authorization_url.to_string(),
+                refresh_url: flow.refresh_url.map(str::to_string),
+                scopes: flow.scopes(),
+                extensions: Default::default(),
+            }),
+            password: self.password.map(|flow| PasswordOAuth2Flow {
+                refresh_url: flow.refresh_url.map(str::to_string),
+                token_url: flow.token_url.to_string(),
+                scopes: flow.scopes(),
+                extensions: Default::default(),
+            }),
+            client_credentials: self
+                .client_credentials
+                .map(|flow| ClientCredentialsOAuth2Flow {
+                    refresh_url: flow.refresh_url.map(str::to_string),
+                    token_url: flow.token_url.to_string(),
+                    scopes: flow.scopes(),
+                    extensions: Default::default(),
+                }),
+            authorization_code: self
+                .authorization_code
+                .map(|flow| AuthorizationCodeOAuth2Flow {
+                    authorization_url: flow.authorization_url.to_string(),
+                    token_url: flow.token_url.to_string(),
+                    refresh_url: flow.refresh_url.map(str::to_string),
+                    scopes: flow.scopes(),
+                    extensions: Default::default(),
+                }),
+            extensions: Default::default(),
+        }
+    }
+}
+
+/// A single OAuth2 flow
+///
+/// Not every flow uses every url, see [`OAuth2Flows`] for details.
+#[derive(Copy, Clone, Debug)]
+pub struct OAuth2Flow {
+    /// The authorization URL to be used for this flow
+    pub authorization_url: &'static str,
+
+    /// The token URL to be used for this flow
+    pub token_url: &'static str,
+
+    /// The URL to be used for obtaining refresh tokens
+    pub refresh_url: Option<&'static str>,
+
+    /// The available scopes as pairs of name and short description
+    pub scopes: &'static [(&'static str, &'static str)],
+}
+
+impl OAuth2Flow {
+    fn scopes(&self) -> IndexMap<String, String> {
+        self.scopes
+            .iter()
+            .map(|(name, description)| (name.to_string(), description.to_string()))
+            .collect()
+    }
+}
+
+/// A [security requirement object](https://spec.openapis.org/oas/v3.0.3#security-requirement-object)
+///
+/// It lists the [`SecurityScheme`]s (by the name they are registered as)
+/// which are **all** required to authorize a request.
+///
+/// Multiple `SecurityRequirement`s on a single handler are alternatives,
+/// i.e. only one of them has to be satisfied.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub struct SecurityRequirement {
+    /// Pairs of a security scheme's name and the scopes required from it
+    ///
+    /// The list of scopes should be empty unless the scheme is [`SecurityScheme::OAuth2`]
+    /// or [`SecurityScheme::OpenIdConnect`].
+    pub schemes: &'static [(&'static str, &'static [&'static str])],
+}
+
+impl SecurityRequirement {
+    /// Constructs a new `SecurityRequirement`
+    pub const fn new(schemes: &'static [(&'static str, &'static [&'static str])]) -> Self {
+        Self { schemes }
+    }
+
+    /// Convert into the [`openapiv3`] representation
+    pub fn to_openapi(&self) -> openapiv3::SecurityRequirement {
+        self.schemes
+            .iter()
+            .map(|(name, scopes)| {
+                (
+                    name.to_string(),
+                    scopes.iter().copied().map(String::from).collect(),
+                )
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,130 @@
+//! Types describing the servers an api is available on
+//!
+//! Servers are declared on a [`SwaggapiPageBuilder`](crate::SwaggapiPageBuilder).
+//! Without any, clients (like the Swagger UI's "Try it out") assume the api is served
+//! from the same host as its documentation.
+//!
+//! If the api's public url is only known at runtime, for example because it runs behind a reverse proxy,
+//! the [`SwaggerUi`](crate::SwaggerUi) can derive it from each request
+//! (see [`SwaggerUi::servers_from_request`](crate::SwaggerUi::servers_from_request)).
+
+use openapiv3::ServerVariable as OpenApiServerVariable;
+
+/// A [server object](https://spec.openapis.org/oas/v3.0.3#server-object)
+///
+/// Unlike [`openapiv3::Server`] this type can be constructed in a `const` context
+/// which is required to use it in a [`SwaggapiPageBuilder`](crate::SwaggapiPageBuilder).
+///
+/// ```rust
+/// # use swaggapi::SwaggapiPageBuilder;
+/// # use swaggapi::servers::{Server, ServerVariable};
+/// static PAGE: SwaggapiPageBuilder = SwaggapiPageBuilder::new().servers(&[
+///     Server::new("https://api.example.com/v1").description("Production"),
+///     Server::new("https://{region}.example.com/v1").variables(&[(
+///         "region",
+///         ServerVariable::new("eu").enumeration(&["eu", "us"]),
+///     )]),
+/// ]);
+/// ```
+#[derive(Copy, Clone, Debug)]
+pub struct Server {
+    /// The url of the target host
+    ///
+    /// It may be relative to the location of the OpenAPI document
+    /// and may contain `{variables}` which are declared in [`Server::variables`].
+    pub url: &'static str,
+
+    /// A short description of the host
+    pub description: Option<&'static str>,
+
+    /// Pairs of a variable's name and its declaration
+    pub variables: &'static [(&'static str, ServerVariable)],
+}
+
+impl Server {
+    /// Constructs a new `Server` with the given url
+    pub const fn new(url: &'static str) -> Self {
+        Self {
+            url,
+            description: None,
+            variables: &[],
+        }
+    }
+
+    /// Sets the description
+    pub const fn description(mut self, text: &'static str) -> Self {
+        self.description = Some(text);
+        self
+    }
+
+    /// Sets the variables used in the url
+    pub const fn variables(mut self, variables: &'static [(&'static str, ServerVariable)]) -> Self {
+        self.variables = variables;
+        self
+    }
+
+    /// Convert into the [`openapiv3`] representation
+    pub fn to_openapi(&self) -> openapiv3::Server {
+        openapiv3::Server {
+            url: self.url.to_string(),
+            description: self.description.map(str::to_string),
+            variables: (!self.variables.is_empty()).then(|| {
+                self.variables
+                    .iter()
+                    .map(|(name, variable)| (name.to_string(), variable.to_openapi()))
+                    .collect()
+            }),
+            extensions: Default::default(),
+        }
+    }
+}
+
+/// A [server variable object](https://spec.openapis.org/oas/v3.0.3#server-variable-object)
+///
+/// A variable for substitution in a [`Server`]'s url.
+#[derive(Copy, Clone, Debug)]
+pub struct ServerVariable {
+    /// The value to use if the client doesn't supply another one
+    pub default: &'static str,
+
+    /// The values the variable is limited to
+    ///
+    /// An empty list doesn't limit the variable.
+    pub enumeration: &'static [&'static str],
+
+    /// A short description of the variable
+    pub description: Option<&'static str>,
+}
+
+impl ServerVariable {
+    /// Constructs a new `ServerVariable` with the given default value
+    pub const fn new(default: &'static str) -> Self {
+        Self {
+            default,
+            enumeration: &[],
+            description: None,
+        }
+    }
+
+    /// Limits the variable to the given values
+    pub const fn enumeration(mut self, values: &'static [&'static str]) -> Self {
+        self.enumeration = values;
+        self
+    }
+
+    /// Sets the description
+    pub const fn description(mut self, text: &'static str) -> Self {
+        self.description = Some(text);
+        self
+    }
+
+    /// Convert into the [`openapiv3`] representation
+    pub fn to_openapi(&self) -> OpenApiServerVariable {
+        OpenApiServerVariable {
+            enumeration: self.enumeration.iter().copied().map(String::from).collect(),
+            default: self.default.to_string(),
+            description: self.description.map(str::to_string),
+            extensions: Default::default(),
+        }
+    }
+}
@@ -0,0 +1,331 @@
+//! Snapshot testing of generated OpenAPI documents
+//!
+//! [`assert_snapshot`] compares a page's document with a file committed to your repository.
+//! This makes every change to your api visible in code review.
+//!
+//! A test would build your `ApiContext`s or the application itself to populate the page and then call:
+//!
+//! ```rust,no_run
+//! # use swaggapi::PageOfEverything;
+//! swaggapi::snapshot::assert_snapshot(PageOfEverything, "openapi.json");
+//! ```
+//!
+//! Run the test with the environment variable `SWAGGAPI_BLESS=1`
+//! to create the file or update it after an intended change.
+//!
+//! # Normalization
+//!
+//! The snapshot should only change when the api does
+//! and not when the handlers are registered in a different order.
+//! Therefore [`snapshot_string`] normalizes the document:
+//!
+//! - the keys of every object are sorted
+//! - the `tags` and `parameters` (by location and name) of operations and path items are sorted
+//! - the `security` requirements (of the document and of operations) and their scopes are sorted
+//! - the `required` properties of schemas are sorted
+//!
+//! The order of every other array is kept because it is either meaningful
+//! (like the values of an `enum`, the items of an `example`
+//! or the top level `tags` which are displayed in their order)
+//! or it doesn't depend on the registration order.
+
+use std::cmp::Ordering;
+use std::env;
+use std::fs;
+use std::mem;
+use std::path::Path;
+
+use serde_json::Map;
+use serde_json::Value;
+
+use crate::internals::walk::walk_document_mut;
+use crate::internals::walk::Position;
+use crate::SwaggapiPage;
+
+/// The environment variable which makes [`assert_snapshot`] write the snapshot instead of comparing it
+pub const BLESS_VAR: &str = "SWAGGAPI_BLESS";
+
+/// Compares a page's document with the snapshot stored in `file`
+///
+/// The document is serialized using [`snapshot_string`].
+/// If [`BLESS_VAR`] is set (to anything other than `0`), the file is (over)written instead.
+///
+/// # Panics
+/// If the file doesn't exist or its content differs from the page's document.
+/// The message contains the first differing line.
+pub fn assert_snapshot(page: impl SwaggapiPage, file: impl AsRef<Path>) {
+    let bless = env::var_os(BLESS_VAR).is_some_and(|value| !value.is_empty() && value != "0");
+    assert_snapshot_string(&snapshot_string(page), file.as_ref(), bless);
+}
+
+/// Implementation of [`assert_snapshot`] independent of the environment
+fn assert_snapshot_string(actual: &str, file: &Path, bless: bool) {
+    if bless {
+        if let Some(parent) = file
+            .parent()
+            .filter(|parent| !parent.as_os_str().is_empty())
+        {
+            if let Err(error) = fs::create_dir_all(parent) {
+                panic!("Couldn't create `{}`: {error}", parent.display());
+            }
+        }
+        if let Err(error) = fs::write(file, actual) {
+            panic!("Couldn't write `{}`: {error}", file.display());
+        }
+        return;
+    }
+
+    let expected = match fs::read_to_string(file) {
+        Ok(expected) => expected.replace("\r\n", "\n"),
+        Err(error) => panic!(
+            "Couldn't read the snapshot `{}`: {error}\n\
+            Run the test with `{BLESS_VAR}=1` to create it",
+            file.display()
+        ),
+    };
+    if expected == actual {
+        return;
+    }
+
+    let mut expected_lines = expected.lines();
+    let mut actual_lines = actual.lines();
+    let mut line = 1;
+    loop {
+        match (expected_lines.next(), actual_lines.next()) {
+            (expected, actual) if expected == actual && expected.is_some() => line += 1,
+            (expected, actual) => panic!(
+                "The OpenAPI document differs from the snapshot `{}` in line {line}:\n\
+                - {}\n\
+                + {}\n\
+                Run the test with `{BLESS_VAR}=1` to update it",
+                file.display(),
+                expected.unwrap_or("<end of file>"),
+                actual.unwrap_or("<end of file>"),
+            ),
+        }
+    }
+}
+
+/// Serializes a page's document deterministically
+///
+/// The document is the one served by [`SwaggapiPage::openapi_json`]
+/// normalized as described in the [module's documentation](self),
+/// so the result doesn't depend on the order the handlers were added in.
+/// It is pretty printed and ends with a newline.
+pub fn snapshot_string(page: impl SwaggapiPage) -> String {
+    let mut json = Value::clone(&page.openapi_json());
+    normalize(&mut json);
+
+    let mut string = serde_json::to_string_pretty(&json).unwrap_or_default();
+    string.push('\n');
+    string
+}
+
+/// Normalizes a document as described in the [module's documentation](self)
+fn normalize(document: &mut Value) {
+    sort_security(document);
+    walk_document_mut(document, &mut |position, _, value| match position {
+        Position::Schema => sort_field(value, "required", cmp_str),
+        Position::Other => {
+            // Only operations and path items contain these keywords
+            sort_field(value, "tags", cmp_str);
+            sort_field(value, "parameters", |a, b| {
+                cmp_str(&a["in"], &b["in"]).then_with(|| cmp_str(&a["name"], &b["name"]))
+            });
+            sort_security(value);
+        }
+    });
+    sort_keys(document);
+}
+
+/// Sorts the requirements in `value`'s `security` array and their scopes
+fn sort_security(value: &mut Value) {
+    let Some(Value::Array(requirements)) = value.get_mut("security") else {
+        return;
+    };
+    for requirement in requirements.iter_mut() {
+        if let Value::Object(requirement) = requirement {
+            for scopes in requirement.values_mut() {
+                if let Value::Array(scopes) = scopes {
+                    scopes.sort_by(cmp_str);
+                }
+            }
+        }
+    }
+    // Compare the requirements by their schemes and scopes
+    requirements.sort_by_cached_key(|requirement| {
+        let mut requirement = requirement.clone();
+        sort_keys(&mut requirement);
+        requirement.to_string()
+    });
+}
+
+/// Sorts the array `value[key]` if it exists
+fn sort_field(value: &mut Value, key: &str, compare: impl FnMut(&Value, &Value) -> Ordering) {
+    if let Some(Value::Array(array)) = value.get_mut(key) {
+        array.sort_by(compare);
+    }
+}
+
+/// Compares two strings (other values are considered equal to each other but less than any string)
+fn cmp_str(a: &Value, b: &Value) -> Ordering {
+    a.as_str().cmp(&b.as_str())
+}
+
+/// Recursively sorts the keys of every object in `value`
+fn sort_keys(value: &mut Value) {
+    match value {
+        Value::Object(object) => {
+            let mut entries: Vec<_> = mem::take(object).into_iter().collect();
+            entries.sort_by(|(a, _), (b, _)| a.cmp(b));
+            *object = entries
+                .into_iter()
+                .map(|(key, mut value)| {
+                    sort_keys(&mut value);
+                    (key, value)
+                })
+                .collect::<Map<_, _>>();
+        }
+        Value::Array(array) => array.iter_mut().for_each(sort_keys),
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::panic;
+    use std::path::PathBuf;
+
+    use serde_json::json;
+
+    use super::*;
+
+    /// Get a path in the temporary directory which is unique to the calling test
+    fn temp_file(name: &str) -> PathBuf {
+        let path = env::temp_dir()
+            .join(format!("swaggapi-snapshot-{}", std::process::id()))
+            .join(name);
+        let _ = fs::remove_file(&path);
+        path
+    }
+
+    fn panic_message(func: impl FnOnce() + panic::UnwindSafe) -> String {
+        let payload = panic::catch_unwind(func).expect_err("Expected a panic");
+        match payload.downcast::<String>() {
+            Ok(message) => *message,
+            Err(payload) => payload.downcast_ref::<&str>().unwrap().to_string(),
+        }
+    }
+
+    #[test]
+    fn bless_writes_the_snapshot() {
+        let file = temp_file("bless/openapi.json");
+        assert_snapshot_string("{}\n", &file, true);
+        assert_eq!(fs::read_to_string(&file).unwrap(), "{}\n");
+
+        // Overwrites an outdated snapshot
+        assert_snapshot_string("[]\n", &file, true);
+        assert_eq!(fs::read_to_string(&file).unwrap(), "[]\n");
+
+        // And the blessed snapshot matches
+        assert_snapshot_string("[]\n", &file, false);
+    }
+
+    #[test]
+    fn missing_snapshot_fails() {
+        let file = temp_file("missing.json");
+        let message = panic_message(|| assert_snapshot_string("{}\n", &file, false));
+        assert!(message.contains("Couldn't read the snapshot"), "{message}");
+        assert!(message.contains(BLESS_VAR), "{message}");
+    }
+
+    #[test]
+    fn mismatch_reports_the_first_differing_line() {
+        let file = temp_file("mismatch.json");
+        fs::create_dir_all(file.parent().unwrap()).unwrap();
+        fs::write(&file, "{\r\n  \"a\": 1,\r\n  \"b\": 2\r\n}\r\n").unwrap();
+
+        // Line endings are ignored
+        assert_snapshot_string("{\n  \"a\": 1,\n  \"b\": 2\n}\n", &file, false);
+
+        let message = panic_message(|| {
+            assert_snapshot_string("{\n  \"a\": 1,\n  \"b\": 3\n}\n", &file, false)
+        });
+        assert!(
+            message.contains("in line 3:\n-   \"b\": 2\n+   \"b\": 3\n"),
+            "{message}"
+        );
+
+        let message = panic_message(|| assert_snapshot_string("{\n  \"a\": 1,\n", &file, false));
+        assert!(
+            message.contains("in line 3:\n-   \"b\": 2\n+ <end of file>\n"),
+            "{message}"
+        );
+    }
+
+    #[test]
+    fn registration_order_is_normalized() {
+        let operation = |tags: Value, parameters: Value, security: Value, required: Value| {
+            json!({
+                "tags": tags,
+                "parameters": parameters,
+                "security": security,
+                "responses": {
+                    "200": {
+                        "description": "",
+                        "content": {
+                            "application/json": {
+                                "schema": { "type": "object", "required": required },
+                                "example": ["b", "a"],
+                            },
+                        },
+                    },
+                },
+            })
+        };
+        let mut document = json!({
+            "tags": [{ "name": "b" }, { "name": "a" }],
+            "security": [{ "b": [] }, { "a": ["write", "read"] }],
+            "paths": {
+                "/": {
+                    "get": operation(
+                        json!(["b", "a"]),
+                        json!([
+                            { "in": "query", "name": "b" },
+                            { "in": "header", "name": "c" },
+                            { "in": "query", "name": "a" },
+                        ]),
+                        json!([{ "a": [] }, { "b": [], "a": [] }]),
+                        json!(["b", "a"]),
+                    ),
+                },
+            },
+        });
+        normalize(&mut document);
+
+        assert_eq!(
+            document,
+            json!({
+                "paths": {
+                    "/": {
+                        "get": operation(
+                            json!(["a", "b"]),
+                            json!([
+                                { "in": "header", "name": "c" },
+                                { "in": "query", "name": "a" },
+                                { "in": "query", "name": "b" },
+                            ]),
+                            json!([{ "a": [], "b": [] }, { "a": [] }]),
+                            json!(["a", "b"]),
+                        ),
+                    },
+                },
+                "security": [{ "a": ["read", "write"] }, { "b": [] }],
+                "tags": [{ "name": "b" }, { "name": "a" }],
+            })
+        );
+        // Objects compare equal regardless of their order, so check the serialized keys
+        let keys: Vec<_> = document.as_object().unwrap().keys().collect();
+        assert_eq!(keys, ["paths", "security", "tags"]);
+    }
+}
@@ -1,11 +1,16 @@
 use std::borrow::Cow;
 use std::sync::Arc;
 
+#[cfg(any(feature = "actix", feature = "axum"))]
+use indexmap::IndexMap;
 use swagger_ui::UrlObject;
 
+#[cfg(any(feature = "actix", feature = "axum"))]
+use crate::internals::ordered_json::OrderedJson;
 use crate::internals::AccessSwaggapiPageBuilder;
+use crate::internals::PageHandle;
 use crate::internals::SwaggapiPageBuilderImpl;
-use crate::page::SwaggapiPageBuilder;
+use crate::registry::Registry;
 use crate::PageOfEverything;
 use crate::SwaggapiPage;
 
@@ -20,13 +25,15 @@ pub struct SwaggerUi {
     /// by the [`SwaggapiPage`]s you added through [`SwaggerUi::page`].
     pub config: swagger_ui::Config,
 
+    /// Should the served documents list the server the request was sent to?
+    servers_from_request: bool,
+
+    /// The registry whose copies of the pages are served
+    registry: Registry,
+
     /// Running count of pages without filenames used to generate unique names for them
     unnamed_pages: usize,
-    pages: Vec<(
-        &'static str,
-        Cow<'static, str>,
-        &'static SwaggapiPageBuilder,
-    )>,
+    pages: Vec<(&'static str, Cow<'static, str>, PageHandle)>,
 }
 impl Default for SwaggerUi {
     /// Normally the swagger ui is served under `"/swagger-ui"` and contains the [`PageOfEverything`]
@@ -34,6 +41,8 @@ impl Default for SwaggerUi {
         Self {
             path: "/swagger-ui",
             config: swagger_ui::Config::default(),
+            servers_from_request: false,
+            registry: Registry::global(),
             unnamed_pages: 0,
             pages: vec![(
                 "Entire API",
@@ -63,14 +72,76 @@ impl SwaggerUi {
     /// Adds a [`SwaggapiPage`] to the ui
     pub fn page(mut self, display_name: &'static str, page: impl SwaggapiPage) -> Self {
         let builder = page.get_builder();
-        let filename = builder.filename.map(Cow::Borrowed).unwrap_or_else(|| {
+        let filename = builder.filename.to_cow().unwrap_or_else(|| {
             self.unnamed_pages += 1;
             Cow::Owned(format!("openapi_{}.json", self.unnamed_pages))
         });
-        self.pages
-            .push((display_name, filename, page.get_builder()));
+        self.pages.push((display_name, filename, builder));
+        self
+    }
+
+    /// Serve the pages' copies stored in a [`Registry`]
+    ///
+    /// Use this together with [`ApiContext::registry`](crate::ApiContext::registry).
+    pub fn registry(mut self, registry: Registry) -> Self {
+        self.registry = registry;
         self
     }
+
+    /// Should the served documents list the server the request for them was sent to?
+    ///
+    /// If enabled, the server's url is derived from the `X-Forwarded-Proto`, `X-Forwarded-Host`
+    /// and `X-Forwarded-Prefix` headers, falling back to `http` and the `Host` header.
+    /// It is inserted before the [`SwaggapiPageBuilder::servers`] and therefore used by default.
+    ///
+    /// This is useful if the api's public url is only known at runtime (for example behind a reverse proxy).
+    /// Only enable it if those headers are set by a trusted proxy or can't be spoofed otherwise.
+    ///
+    /// Defaults to `false`.
+    pub fn servers_from_request(mut self, enabled: bool) -> Self {
+        self.servers_from_request = enabled;
+        self
+    }
+}
+
+/// Inserts the server a request was sent to at the beginning of a document's `servers`
+///
+/// `header` looks up a request header by its lowercase name.
+/// See [`SwaggerUi::servers_from_request`] for the headers used.
+#[cfg(any(feature = "actix", feature = "axum"))]
+fn with_request_server<'a>(
+    json: Arc<OrderedJson>,
+    header: impl Fn(&str) -> Option<&'a str>,
+) -> Arc<OrderedJson> {
+    // Proxies append to the X-Forwarded headers, so the first value is set by the outermost one
+    let header = |name| {
+        header(name)
+            .and_then(|value| value.split(',').next())
+            .map(str::trim)
+    };
+
+    let Some(host) = header("x-forwarded-host").or_else(|| header("host")) else {
+        return json;
+    };
+    let proto = header("x-forwarded-proto").unwrap_or("http");
+    let prefix = header("x-forwarded-prefix")
+        .unwrap_or("")
+        .trim_end_matches('/');
+    let server = OrderedJson::Object(IndexMap::from([(
+        "url".to_string(),
+        OrderedJson::String(format!("{proto}://{host}{prefix}")),
+    )]));
+
+    let mut json = OrderedJson::clone(&json);
+    if let OrderedJson::Object(document) = &mut json {
+        match document.get_mut("servers") {
+            Some(OrderedJson::Array(servers)) => servers.insert(0, server),
+            _ => {
+                document.insert("servers".to_string(), OrderedJson::Array(vec![server]));
+            }
+        }
+    }
+    Arc::new(json)
 }
 
 #[cfg(feature = "actix")]
@@ -81,6 +152,7 @@ const _: () = {
     use actix_web::web::scope;
     use actix_web::web::Json;
     use actix_web::web::Redirect;
+    use actix_web::HttpRequest;
     use actix_web::HttpResponse;
     use actix_web::Responder;
     use actix_web::Route;
@@ -101,10 +173,20 @@ const _: () = {
                     serve_static(|| Redirect::to("index.html?configUrl=config.json")),
                 )
                 .route("config.json", serve_static(move || Json(config)));
+            let servers_from_request = self.servers_from_request;
             for (_, file_name, builder) in self.pages {
+                let builder = self.registry.page(builder);
                 scope = scope.route(
                     &file_name,
-                    serve_static(|| Json(SwaggapiPageBuilderImpl::build(builder))),
+                    web::get().to(move |request: HttpRequest| {
+                        let mut json = SwaggapiPageBuilderImpl::build_ordered_json(&builder);
+                        if servers_from_request {
+                            json = with_request_server(json, |name| {
+                                request.headers().get(name)?.to_str().ok()
+                            });
+                        }
+                        std::future::ready(Json(json))
+                    }),
                 );
             }
             for file_name in swagger_ui::Assets::iter() {
@@ -129,6 +211,7 @@ const _: () = {
 #[cfg(feature = "axum")]
 const _: () = {
     use axum::body::Body;
+    use axum::http::HeaderMap;
     use axum::response::IntoResponse;
     use axum::response::Redirect;
     use axum::response::Response;
@@ -161,10 +244,19 @@ const _: () = {
                     &format!("{normal_path}/config.json"),
                     serve_static(move || Json(config)),
                 );
+            let servers_from_request = value.servers_from_request;
             for (_, file_name, builder) in value.pages {
+                let builder = value.registry.page(builder);
                 router = router.route(
                     &format!("{normal_path}/{file_name}"),
-                    serve_static(|| Json(SwaggapiPageBuilderImpl::build(builder))),
+                    MethodRouter::new().get(move |headers: HeaderMap| {
+                        let mut json = SwaggapiPageBuilderImpl::build_ordered_json(&builder);
+                        if servers_from_request {
+                            json =
+                                with_request_server(json, |name| headers.get(name)?.to_str().ok());
+                        }
+                        std::future::ready(Json(json))
+                    }),
                 );
             }
             for file_name in swagger_ui::Assets::iter() {
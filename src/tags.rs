@@ -0,0 +1,104 @@
+//! Types describing the tags handlers are grouped by
+//!
+//! Handlers are tagged using plain strings (`#[get("/x", tags("users"))]` or [`ApiContext::tag`](crate::ApiContext::tag)).
+//! A [`Tag`] adds metadata like a description to such a string.
+//! It is declared on a [`SwaggapiPageBuilder`](crate::SwaggapiPageBuilder)
+//! or an [`ApiContext`](crate::ApiContext) using [`ApiContext::tag_definition`](crate::ApiContext::tag_definition).
+
+use serde_json::json;
+use serde_json::Value;
+
+/// A [tag object](https://spec.openapis.org/oas/v3.0.3#tag-object)
+///
+/// Unlike [`openapiv3::Tag`] this type can be constructed in a `const` context
+/// which is required to use it in a [`SwaggapiPageBuilder`](crate::SwaggapiPageBuilder).
+///
+/// ```rust
+/// # use swaggapi::SwaggapiPageBuilder;
+/// # use swaggapi::tags::Tag;
+/// static PAGE: SwaggapiPageBuilder = SwaggapiPageBuilder::new().tags(&[
+///     Tag::new("users").description("Everything about your users"),
+///     Tag::new("admin")
+///         .description("Endpoints only available to admins")
+///         .external_docs("https://example.com/docs/admin", Some("The admin guide")),
+/// ]);
+/// ```
+#[derive(Copy, Clone, Debug)]
+pub struct Tag {
+    /// The name of the tag
+    pub name: &'static str,
+
+    /// A short description for the tag
+    pub description: Option<&'static str>,
+
+    /// Pair of an url to additional external documentation and its short description
+    pub external_docs: Option<(&'static str, Option<&'static str>)>,
+}
+
+impl Tag {
+    /// Constructs a new `Tag` with the given name
+    pub const fn new(name: &'static str) -> Self {
+        Self {
+            name,
+            description: None,
+            external_docs: None,
+        }
+    }
+
+    /// Sets the description
+    pub const fn description(mut self, text: &'static str) -> Self {
+        self.description = Some(text);
+        self
+    }
+
+    /// Sets the url of additional external documentation and its optional description
+    pub const fn external_docs(mut self, url: &'static str, description: Option<&'static str>) -> Self {
+        self.external_docs = Some((url, description));
+        self
+    }
+
+    /// Convert into the [`openapiv3`] representation
+    pub fn to_openapi(&self) -> openapiv3::Tag {
+        openapiv3::Tag {
+            name: self.name.to_string(),
+            description: self.description.map(str::to_string),
+            external_docs: self
+                .external_docs
+                .map(|(url, description)| openapiv3::ExternalDocumentation {
+                    description: description.map(str::to_string),
+                    url: url.to_string(),
+                    extensions: Default::default(),
+                }),
+            extensions: Default::default(),
+        }
+    }
+}
+
+/// A group of tags
+///
+/// Tag groups are not part of the OpenAPI specification,
+/// they are emitted as the `x-tagGroups` extension which is used by [Redoc](https://redocly.com/docs-legacy/api-reference-docs/specification-extensions/x-tag-groups)
+/// to display a second level of navigation.
+#[derive(Copy, Clone, Debug)]
+pub struct TagGroup {
+    /// The name of the group
+    pub name: &'static str,
+
+    /// The names of the tags in the group
+    pub tags: &'static [&'static str],
+}
+
+impl TagGroup {
+    /// Constructs a new `TagGroup`
+    pub const fn new(name: &'static str, tags: &'static [&'static str]) -> Self {
+        Self { name, tags }
+    }
+
+    /// Convert into the json representation used in `x-tagGroups`
+    pub fn to_json(&self) -> Value {
+        json!({
+            "name": self.name,
+            "tags": self.tags,
+        })
+    }
+}
@@ -7,12 +7,26 @@
 #[derive(Copy, Clone, Debug)]
 pub struct SchemalessJson<T>(pub T);
 
+/// Response which sends `T` with the status code `201 Created`
+#[derive(Copy, Clone, Debug)]
+pub struct Created<T>(pub T);
+
+/// Response which sends `T` with the status code `202 Accepted`
+#[derive(Copy, Clone, Debug)]
+pub struct Accepted<T>(pub T);
+
+/// Empty response with the status code `204 No Content`
+#[derive(Copy, Clone, Debug)]
+pub struct NoContent;
+
 #[cfg(feature = "actix")]
 const _: () = {
     use std::future::Future;
     use std::pin::Pin;
 
+    use actix_web::body::BoxBody;
     use actix_web::dev::Payload;
+    use actix_web::http::StatusCode;
     use actix_web::web::Json;
     use actix_web::FromRequest;
     use actix_web::HttpRequest;
@@ -44,12 +58,39 @@ const _: () = {
             Json(self.0).respond_to(req)
         }
     }
+
+    impl<T: Responder> Responder for Created<T> {
+        type Body = T::Body;
+
+        fn respond_to(self, req: &HttpRequest) -> HttpResponse<Self::Body> {
+            let mut response = self.0.respond_to(req);
+            *response.status_mut() = StatusCode::CREATED;
+            response
+        }
+    }
+    impl<T: Responder> Responder for Accepted<T> {
+        type Body = T::Body;
+
+        fn respond_to(self, req: &HttpRequest) -> HttpResponse<Self::Body> {
+            let mut response = self.0.respond_to(req);
+            *response.status_mut() = StatusCode::ACCEPTED;
+            response
+        }
+    }
+    impl Responder for NoContent {
+        type Body = BoxBody;
+
+        fn respond_to(self, _req: &HttpRequest) -> HttpResponse<Self::Body> {
+            HttpResponse::NoContent().finish()
+        }
+    }
 };
 
 #[cfg(feature = "axum")]
 const _: () = {
     use axum::extract::FromRequest;
     use axum::extract::Request;
+    use axum::http::StatusCode;
     use axum::response::IntoResponse;
     use axum::response::Response;
     use axum::Json;
@@ -75,4 +116,20 @@ const _: () = {
             Json(self.0).into_response()
         }
     }
+
+    impl<T: IntoResponse> IntoResponse for Created<T> {
+        fn into_response(self) -> Response {
+            (StatusCode::CREATED, self.0).into_response()
+        }
+    }
+    impl<T: IntoResponse> IntoResponse for Accepted<T> {
+        fn into_response(self) -> Response {
+            (StatusCode::ACCEPTED, self.0).into_response()
+        }
+    }
+    impl IntoResponse for NoContent {
+        fn into_response(self) -> Response {
+            StatusCode::NO_CONTENT.into_response()
+        }
+    }
 };
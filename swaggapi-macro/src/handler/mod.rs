@@ -10,10 +10,13 @@ use quote::quote;
 use quote::quote_spanned;
 use quote::ToTokens;
 use syn::FnArg;
+use syn::GenericArgument;
 use syn::ItemFn;
 use syn::Meta;
 use syn::MetaNameValue;
+use syn::PathArguments;
 use syn::ReturnType;
+use syn::Type;
 
 mod parse;
 
@@ -59,6 +62,15 @@ pub fn handler(
             Delimiter::Bracket,
             TokenStream::new(),
         )));
+    let security = keyword
+        .remove(&Ident::new("security", Span::call_site()))
+        .map(security_requirements)
+        .unwrap_or_default();
+    let state = keyword.remove(&Ident::new("state", Span::call_site()));
+    let operation_id = match keyword.remove(&Ident::new("operation_id", Span::call_site())) {
+        Some(operation_id) => quote! { ::std::option::Option::Some(#operation_id) },
+        None => quote! { ::std::option::Option::None },
+    };
     #[cfg(feature = "tracing-instrument")]
     let level = keyword
         .remove(&Ident::new("instrument_level", Span::call_site()))
@@ -93,6 +105,14 @@ pub fn handler(
             FnArg::Typed(arg) => Some(&arg.ty),
         })
         .collect::<Vec<_>>();
+    let state = match state {
+        Some(TokenTree::Group(group)) => group.stream(),
+        Some(state) => state.into_token_stream(),
+        None => argument_type
+            .iter()
+            .find_map(|ty| state_type(ty))
+            .unwrap_or_else(|| quote! { () }),
+    };
     let return_type = match sig.output {
         ReturnType::Default => quote! { () },
         ReturnType::Type(_, return_type) => return_type.into_token_stream(),
@@ -160,17 +180,75 @@ pub fn handler(
                     #doc,
                 )*],
                 ident: #ident,
+                module_path: ::std::module_path!(),
+                operation_id: #operation_id,
                 tags: &#tags,
+                security: &[#(
+                    #security,
+                )*],
                 responses: <#return_type as ::swaggapi::as_responses::AsResponses>::responses,
                 handler_arguments: &FNS,
                 actix: ::swaggapi::impl_Foo_actix!(
                     ::swaggapi::internals::HttpMethod::#method, #func_ident
                 ),
                 axum: ::swaggapi::impl_Foo_axum!(
-                    ::swaggapi::internals::HttpMethod::#method, #func_ident
+                    ::swaggapi::internals::HttpMethod::#method, #func_ident, #state
                 ),
             }
 
         };
     }
 }
+
+/// Splits the list passed to `security(...)` into its elements
+/// and converts string literals into `SecurityRequirement`s requiring only the named scheme.
+///
+/// Every other element is expected to be an expression evaluating to a `SecurityRequirement`.
+fn security_requirements(list: TokenTree) -> Vec<TokenStream> {
+    let list = match list {
+        TokenTree::Group(group) => group.stream(),
+        other => other.into_token_stream(),
+    };
+
+    let mut elements = vec![Vec::new()];
+    for token in list {
+        match &token {
+            TokenTree::Punct(punct) if punct.as_char() == ',' => elements.push(Vec::new()),
+            _ => elements.last_mut().unwrap().push(token),
+        }
+    }
+
+    elements
+        .into_iter()
+        .filter(|element| !element.is_empty())
+        .map(|element| match element.as_slice() {
+            [TokenTree::Literal(name)] if name.to_string().starts_with('"') => quote! {
+                ::swaggapi::security::SecurityRequirement::new(&[(#name, &[])])
+            },
+            _ => TokenStream::from_iter(element),
+        })
+        .collect()
+}
+
+/// Extracts `S` from an argument of type `State<S>`
+///
+/// This only looks at the argument's syntax, so it can't detect renamed imports or type aliases.
+/// The `state` key can be used in such cases.
+fn state_type(argument_type: &Type) -> Option<TokenStream> {
+    let Type::Path(path) = argument_type else {
+        return None;
+    };
+    let segment = path.path.segments.last()?;
+    if segment.ident != "State" {
+        return None;
+    }
+    let PathArguments::AngleBracketed(arguments) = &segment.arguments else {
+        return None;
+    };
+    match arguments.args.first()? {
+        GenericArgument::Type(state) if arguments.args.len() == 1 => {
+            Some(state.into_token_stream())
+        }
+        _ => None,
+    }
+}
@@ -0,0 +1,125 @@
+use proc_macro2::Literal;
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::ext::IdentExt;
+use syn::parse2;
+use syn::Data;
+use syn::DeriveInput;
+use syn::Fields;
+use syn::LitStr;
+
+use crate::serde_attrs;
+
+pub fn headers(input: TokenStream) -> TokenStream {
+    match parse2::<DeriveInput>(input).and_then(from_headers) {
+        Ok(tokens) => tokens,
+        Err(err) => err.into_compile_error(),
+    }
+}
+
+fn from_headers(input: DeriveInput) -> syn::Result<TokenStream> {
+    let DeriveInput {
+        attrs,
+        ident,
+        generics,
+        data,
+        ..
+    } = input;
+    let fields = match data {
+        Data::Struct(data) => match data.fields {
+            Fields::Named(fields) => fields.named,
+            fields => {
+                return Err(syn::Error::new_spanned(
+                    fields,
+                    "FromHeaders can only be derived for structs with named fields",
+                ))
+            }
+        },
+        _ => {
+            return Err(syn::Error::new_spanned(
+                ident,
+                "FromHeaders can only be derived for structs",
+            ))
+        }
+    };
+
+    let mut field_ident = Vec::new();
+    let mut field_type = Vec::new();
+    let mut parts = Vec::new();
+    let rename_all = serde_attrs::rename_all(&attrs)?;
+    for field in &fields {
+        let ident = field.ident.as_ref().expect("Fields should be named");
+        let property = serde_attrs::field_name(field, rename_all.as_ref())?;
+
+        let mut location = None;
+        let mut name = None;
+        for attr in &field.attrs {
+            let is_cookie = if attr.path().is_ident("header") {
+                false
+            } else if attr.path().is_ident("cookie") {
+                true
+            } else {
+                continue;
+            };
+            if location.replace(is_cookie).is_some() {
+                return Err(syn::Error::new_spanned(
+                    attr,
+                    "A field can only be either a single header or a single cookie",
+                ));
+            }
+            if matches!(attr.meta, syn::Meta::Path(_)) {
+                continue;
+            }
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("name") {
+                    name = Some(meta.value()?.parse::<LitStr>()?.value());
+                    Ok(())
+                } else {
+                    Err(meta.error("Unknown argument, expected `name`"))
+                }
+            })?;
+        }
+
+        let is_cookie = location.unwrap_or(false);
+        let name = name.unwrap_or_else(|| {
+            let ident = ident.unraw().to_string();
+            if is_cookie {
+                ident
+            } else {
+                ident.replace('_', "-")
+            }
+        });
+        let location = if is_cookie {
+            quote! { ::swaggapi::headers::HeaderLocation::Cookie }
+        } else {
+            quote! { ::swaggapi::headers::HeaderLocation::Header }
+        };
+        let property = Literal::string(&property);
+        let name = Literal::string(&name);
+        parts.push(quote! {
+            ::swaggapi::headers::HeaderPart {
+                property: #property,
+                name: #name,
+                location: #location,
+            }
+        });
+        field_ident.push(ident);
+        field_type.push(&field.ty);
+    }
+    let index = 0..parts.len();
+
+    let (impl_generics, type_generics, where_clause) = generics.split_for_impl();
+    Ok(quote! {
+        impl #impl_generics ::swaggapi::headers::FromHeaders for #ident #type_generics #where_clause {
+            const PARTS: &'static [::swaggapi::headers::HeaderPart] = &[#(#parts,)*];
+
+            fn from_headers(
+                headers: &::swaggapi::headers::RequestHeaders,
+            ) -> ::std::result::Result<Self, ::swaggapi::headers::HeaderError> {
+                ::std::result::Result::Ok(Self {#(
+                    #field_ident: headers.parse::<#field_type>(&Self::PARTS[#index])?,
+                )*})
+            }
+        }
+    })
+}
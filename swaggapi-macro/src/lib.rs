@@ -1,5 +1,9 @@
 mod handler;
+mod headers;
+mod multipart;
 mod page;
+mod responses;
+mod serde_attrs;
 
 use proc_macro::TokenStream;
 
@@ -8,6 +12,21 @@ pub fn derive_page(input: TokenStream) -> TokenStream {
     page::page(input.into()).into()
 }
 
+#[proc_macro_derive(AsResponses, attributes(response))]
+pub fn derive_responses(input: TokenStream) -> TokenStream {
+    responses::responses(input.into()).into()
+}
+
+#[proc_macro_derive(FromHeaders, attributes(header, cookie))]
+pub fn derive_headers(input: TokenStream) -> TokenStream {
+    headers::headers(input.into()).into()
+}
+
+#[proc_macro_derive(FromMultipart, attributes(serde))]
+pub fn derive_multipart(input: TokenStream) -> TokenStream {
+    multipart::multipart(input.into()).into()
+}
+
 #[proc_macro_attribute]
 pub fn handler(args: TokenStream, input: TokenStream) -> TokenStream {
     handler::handler(args.into(), input.into(), None).into()
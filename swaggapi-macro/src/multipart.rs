@@ -0,0 +1,127 @@
+use proc_macro2::Literal;
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::parse2;
+use syn::Data;
+use syn::DeriveInput;
+use syn::Expr;
+use syn::ExprLit;
+use syn::Fields;
+use syn::Lit;
+use syn::Meta;
+use syn::MetaNameValue;
+
+use crate::serde_attrs;
+
+pub fn multipart(input: TokenStream) -> TokenStream {
+    match parse2::<DeriveInput>(input).and_then(from_multipart) {
+        Ok(tokens) => tokens,
+        Err(err) => err.into_compile_error(),
+    }
+}
+
+fn from_multipart(input: DeriveInput) -> syn::Result<TokenStream> {
+    let DeriveInput {
+        attrs,
+        ident,
+        generics,
+        data,
+        ..
+    } = input;
+    let fields = match data {
+        Data::Struct(data) => match data.fields {
+            Fields::Named(fields) => fields.named,
+            fields => {
+                return Err(syn::Error::new_spanned(
+                    fields,
+                    "FromMultipart can only be derived for structs with named fields",
+                ))
+            }
+        },
+        _ => {
+            return Err(syn::Error::new_spanned(
+                ident,
+                "FromMultipart can only be derived for structs",
+            ))
+        }
+    };
+
+    let field_ident = fields
+        .iter()
+        .map(|field| field.ident.as_ref().expect("Fields should be named"))
+        .collect::<Vec<_>>();
+    let rename_all = serde_attrs::rename_all(&attrs)?;
+    let field_name = fields
+        .iter()
+        .map(|field| {
+            Ok(Literal::string(&serde_attrs::field_name(
+                field,
+                rename_all.as_ref(),
+            )?))
+        })
+        .collect::<syn::Result<Vec<_>>>()?;
+    let field_description = fields
+        .iter()
+        .map(|field| match description(&field.attrs) {
+            Some(description) => {
+                let description = Literal::string(&description);
+                quote! { ::std::option::Option::Some(#description) }
+            }
+            None => quote! { ::std::option::Option::None },
+        })
+        .collect::<Vec<_>>();
+    let field_type = fields.iter().map(|field| &field.ty).collect::<Vec<_>>();
+
+    let (impl_generics, type_generics, where_clause) = generics.split_for_impl();
+    Ok(quote! {
+        impl #impl_generics ::swaggapi::multipart::FromMultipart for #ident #type_generics #where_clause {
+            fn parts(
+                gen: &mut ::swaggapi::internals::SchemaGenerator,
+            ) -> ::std::vec::Vec<::swaggapi::multipart::MultipartPart> {
+                ::std::vec![#(
+                    ::swaggapi::multipart::MultipartPart {
+                        name: #field_name,
+                        description: #field_description,
+                        schema: <#field_type as ::swaggapi::multipart::MultipartField>::schema(gen),
+                        required: <#field_type as ::swaggapi::multipart::MultipartField>::required(),
+                        content_type: <#field_type as ::swaggapi::multipart::MultipartField>::content_type(),
+                    },
+                )*]
+            }
+
+            fn from_multipart(
+                mut parts: ::swaggapi::multipart::MultipartParts,
+            ) -> ::std::result::Result<Self, ::swaggapi::multipart::MultipartError> {
+                ::std::result::Result::Ok(Self {#(
+                    #field_ident: parts.take::<#field_type>(#field_name)?,
+                )*})
+            }
+        }
+    })
+}
+
+/// Joins a field's doc comments into a description
+fn description(attrs: &[syn::Attribute]) -> Option<String> {
+    let lines = attrs
+        .iter()
+        .filter_map(|attr| match &attr.meta {
+            Meta::NameValue(MetaNameValue {
+                path,
+                value:
+                    Expr::Lit(ExprLit {
+                        lit: Lit::Str(line),
+                        ..
+                    }),
+                ..
+            }) if path.is_ident("doc") => Some(line.value()),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+    let description = lines
+        .iter()
+        .map(|line| line.trim())
+        .collect::<Vec<_>>()
+        .join("\n");
+    let description = description.trim();
+    (!description.is_empty()).then(|| description.to_string())
+}
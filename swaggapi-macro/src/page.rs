@@ -11,6 +11,8 @@ use syn::bracketed;
 use syn::parse::Parse;
 use syn::parse::ParseStream;
 use syn::parse2;
+use syn::parse_quote;
+use syn::Expr;
 use syn::MetaList;
 use syn::Token;
 use syn::Visibility;
@@ -24,7 +26,7 @@ pub fn page(input: TokenStream) -> TokenStream {
 
 struct Page {
     ident: Ident,
-    kwargs: HashMap<Ident, TokenTree>,
+    kwargs: HashMap<Ident, Expr>,
 }
 
 impl Parse for Page {
@@ -63,7 +65,10 @@ impl Parse for Page {
 
         kwargs
             .entry(Ident::new("filename", Span::call_site()))
-            .or_insert_with(|| TokenTree::Literal(Literal::string(&format!("{ident}.json"))));
+            .or_insert_with(|| {
+                let filename = Literal::string(&format!("{ident}.json"));
+                parse_quote!(#filename)
+            });
 
         Ok(Self { ident, kwargs })
     }
@@ -76,13 +81,13 @@ impl ToTokens for Page {
         let values = kwargs.values();
         tokens.extend(quote! {
             impl ::swaggapi::internals::AccessSwaggapiPageBuilder for #ident {
-                fn get_builder(&self) -> &'static ::swaggapi::SwaggapiPageBuilder {
+                fn get_builder(&self) -> ::swaggapi::internals::PageHandle {
                     static BUILDER: ::swaggapi::SwaggapiPageBuilder = ::swaggapi::SwaggapiPageBuilder::new()
                     #(
                         .#keys(#values)
                     )*
                     ;
-                    &BUILDER
+                    ::swaggapi::internals::PageHandle::Static(&BUILDER)
                 }
             }
         });
@@ -0,0 +1,222 @@
+use proc_macro2::Literal;
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::parse2;
+use syn::Attribute;
+use syn::Data;
+use syn::DeriveInput;
+use syn::Expr;
+use syn::ExprLit;
+use syn::Fields;
+use syn::Lit;
+use syn::LitInt;
+use syn::LitStr;
+use syn::Meta;
+
+pub fn responses(input: TokenStream) -> TokenStream {
+    match parse2::<DeriveInput>(input).and_then(as_responses) {
+        Ok(tokens) => tokens,
+        Err(err) => err.into_compile_error(),
+    }
+}
+
+fn as_responses(input: DeriveInput) -> syn::Result<TokenStream> {
+    let DeriveInput {
+        ident,
+        generics,
+        data,
+        ..
+    } = input;
+    let Data::Enum(data) = data else {
+        return Err(syn::Error::new_spanned(
+            ident,
+            "AsResponses can only be derived for enums",
+        ));
+    };
+
+    let mut responses = Vec::new();
+    for variant in &data.variants {
+        let mut status = None;
+        let mut description = None;
+        let mut found = false;
+        for attr in &variant.attrs {
+            if !attr.path().is_ident("response") {
+                continue;
+            }
+            found = true;
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("status") {
+                    status = Some(meta.value()?.parse::<LitInt>()?.base10_parse::<u16>()?);
+                    Ok(())
+                } else if meta.path.is_ident("description") {
+                    description = Some(meta.value()?.parse::<LitStr>()?.value());
+                    Ok(())
+                } else {
+                    Err(meta.error("Unknown argument, expected `status` or `description`"))
+                }
+            })?;
+        }
+        let Some(status) = status else {
+            return Err(syn::Error::new_spanned(
+                variant,
+                if found {
+                    "Missing `status` in `#[response(...)]`"
+                } else {
+                    "Missing `#[response(status = ...)]`"
+                },
+            ));
+        };
+        let description = Literal::string(
+            &description
+                .or_else(|| doc_comment(&variant.attrs))
+                .unwrap_or_else(|| variant.ident.to_string()),
+        );
+
+        responses.push(match &variant.fields {
+            Fields::Unit => quote! {
+                ::swaggapi::as_responses::empty_response(
+                    ::swaggapi::re_exports::openapiv3::StatusCode::Code(#status),
+                    ::std::string::String::from(#description),
+                )
+            },
+            Fields::Unnamed(fields) if fields.unnamed.len() == 1 => {
+                let ty = &fields.unnamed[0].ty;
+                quote! {
+                    ::swaggapi::as_responses::simple_responses([
+                        ::swaggapi::as_responses::SimpleResponse {
+                            status_code: ::swaggapi::re_exports::openapiv3::StatusCode::Code(#status),
+                            mime_type: ::swaggapi::re_exports::mime::APPLICATION_JSON,
+                            description: ::std::string::String::from(#description),
+                            media_type: ::std::option::Option::Some(::swaggapi::re_exports::openapiv3::MediaType {
+                                schema: ::std::option::Option::Some(gen.generate::<#ty>()),
+                                ..::std::default::Default::default()
+                            }),
+                        },
+                    ])
+                }
+            }
+            fields => {
+                return Err(syn::Error::new_spanned(
+                    fields,
+                    "AsResponses only supports unit variants and variants with a single unnamed field",
+                ))
+            }
+        });
+    }
+
+    let (impl_generics, type_generics, where_clause) = generics.split_for_impl();
+    Ok(quote! {
+        impl #impl_generics ::swaggapi::as_responses::AsResponses for #ident #type_generics #where_clause {
+            fn responses(
+                gen: &mut ::swaggapi::internals::SchemaGenerator,
+            ) -> ::swaggapi::re_exports::openapiv3::Responses {
+                let mut responses = ::swaggapi::re_exports::openapiv3::Responses::default();
+                #(
+                    ::swaggapi::as_responses::merge_responses(&mut responses, #responses);
+                )*
+                responses
+            }
+        }
+    })
+}
+
+/// Joins a variant's doc comments into a single string
+fn doc_comment(attrs: &[Attribute]) -> Option<String> {
+    let lines = attrs
+        .iter()
+        .filter_map(|attr| match &attr.meta {
+            Meta::NameValue(meta) if meta.path.is_ident("doc") => match &meta.value {
+                Expr::Lit(ExprLit {
+                    lit: Lit::Str(doc), ..
+                }) => Some(doc.value()),
+                _ => None,
+            },
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+    let doc = lines
+        .iter()
+        .map(|line| line.strip_prefix(' ').unwrap_or(line))
+        .collect::<Vec<_>>()
+        .join("\n");
+    let doc = doc.trim();
+    (!doc.is_empty()).then(|| doc.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use syn::parse_quote;
+
+    use super::*;
+
+    fn error(input: DeriveInput) -> String {
+        as_responses(input)
+            .map(|_| ())
+            .expect_err("Expected an error")
+            .to_string()
+    }
+
+    #[test]
+    fn structs_are_rejected() {
+        assert_eq!(
+            error(parse_quote! { struct ApiError; }),
+            "AsResponses can only be derived for enums"
+        );
+    }
+
+    #[test]
+    fn named_fields_are_rejected() {
+        assert_eq!(
+            error(parse_quote! {
+                enum ApiError {
+                    #[response(status = 400)]
+                    Invalid { field: String },
+                }
+            }),
+            "AsResponses only supports unit variants and variants with a single unnamed field"
+        );
+        assert_eq!(
+            error(parse_quote! {
+                enum ApiError {
+                    #[response(status = 400)]
+                    Invalid(String, String),
+                }
+            }),
+            "AsResponses only supports unit variants and variants with a single unnamed field"
+        );
+    }
+
+    #[test]
+    fn missing_status_is_rejected() {
+        assert_eq!(
+            error(parse_quote! {
+                enum ApiError {
+                    NotFound,
+                }
+            }),
+            "Missing `#[response(status = ...)]`"
+        );
+        assert_eq!(
+            error(parse_quote! {
+                enum ApiError {
+                    #[response(description = "Not found")]
+                    NotFound,
+                }
+            }),
+            "Missing `status` in `#[response(...)]`"
+        );
+    }
+
+    #[test]
+    fn unknown_arguments_are_rejected() {
+        assert_eq!(
+            error(parse_quote! {
+                enum ApiError {
+                    #[response(status = 404, content_type = "text/plain")]
+                    NotFound,
+                }
+            }),
+            "Unknown argument, expected `status` or `description`"
+        );
+    }
+}
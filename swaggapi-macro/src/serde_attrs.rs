@@ -0,0 +1,140 @@
+//! Mirrors the names serde (and therefore schemars) uses for a struct's fields
+
+use syn::ext::IdentExt;
+use syn::meta::ParseNestedMeta;
+use syn::Attribute;
+use syn::Expr;
+use syn::Field;
+use syn::LitStr;
+use syn::Token;
+
+/// The name a field has in its struct's `JsonSchema`
+///
+/// This is its identifier (without `r#`) changed by `#[serde(rename)]` or the struct's `#[serde(rename_all)]`.
+/// Like schemars, the deserialization name is used and `#[schemars(...)]` overrides `#[serde(...)]`.
+pub fn field_name(field: &Field, rename_all: Option<&RenameRule>) -> syn::Result<String> {
+    let mut rename = None;
+    parse_renames(&field.attrs, "rename", |value| {
+        rename = Some(value.value());
+        Ok(())
+    })?;
+    let ident = field
+        .ident
+        .as_ref()
+        .expect("Fields should be named")
+        .unraw()
+        .to_string();
+    Ok(rename.unwrap_or_else(|| match rename_all {
+        Some(rule) => rule.apply(&ident),
+        None => ident,
+    }))
+}
+
+/// Get the rule from a struct's `#[serde(rename_all)]`
+pub fn rename_all(attrs: &[Attribute]) -> syn::Result<Option<RenameRule>> {
+    let mut rule = None;
+    parse_renames(attrs, "rename_all", |value| {
+        rule = Some(
+            RenameRule::from_str(&value.value())
+                .ok_or_else(|| syn::Error::new_spanned(&value, "Unknown case for `rename_all`"))?,
+        );
+        Ok(())
+    })?;
+    Ok(rule)
+}
+
+/// A case conversion from `#[serde(rename_all)]`
+pub enum RenameRule {
+    Lower,
+    Upper,
+    Pascal,
+    Camel,
+    Snake,
+    ScreamingSnake,
+    Kebab,
+    ScreamingKebab,
+}
+
+impl RenameRule {
+    fn from_str(rule: &str) -> Option<Self> {
+        Some(match rule {
+            "lowercase" => Self::Lower,
+            "UPPERCASE" => Self::Upper,
+            "PascalCase" => Self::Pascal,
+            "camelCase" => Self::Camel,
+            "snake_case" => Self::Snake,
+            "SCREAMING_SNAKE_CASE" => Self::ScreamingSnake,
+            "kebab-case" => Self::Kebab,
+            "SCREAMING-KEBAB-CASE" => Self::ScreamingKebab,
+            _ => return None,
+        })
+    }
+
+    /// Applies the rule to a field's name (which is expected to be in snake case)
+    pub fn apply(&self, field: &str) -> String {
+        match self {
+            Self::Lower | Self::Snake => field.to_string(),
+            Self::Upper | Self::ScreamingSnake => field.to_ascii_uppercase(),
+            Self::Pascal => field
+                .split('_')
+                .map(|word| {
+                    let mut chars = word.chars();
+                    chars
+                        .next()
+                        .map(|first| first.to_ascii_uppercase().to_string() + chars.as_str())
+                        .unwrap_or_default()
+                })
+                .collect(),
+            Self::Camel => {
+                let pascal = Self::Pascal.apply(field);
+                let mut chars = pascal.chars();
+                chars
+                    .next()
+                    .map(|first| first.to_ascii_lowercase().to_string() + chars.as_str())
+                    .unwrap_or_default()
+            }
+            Self::Kebab => field.replace('_', "-"),
+            Self::ScreamingKebab => field.to_ascii_uppercase().replace('_', "-"),
+        }
+    }
+}
+
+/// Calls `func` for every `key = ".."` or `key(deserialize = "..")` in `#[serde]` and `#[schemars]` attributes
+///
+/// The `#[schemars]` attributes are visited last, so they override the `#[serde]` ones.
+fn parse_renames(
+    attrs: &[Attribute],
+    key: &str,
+    mut func: impl FnMut(LitStr) -> syn::Result<()>,
+) -> syn::Result<()> {
+    for attr_name in ["serde", "schemars"] {
+        for attr in attrs.iter().filter(|attr| attr.path().is_ident(attr_name)) {
+            attr.parse_nested_meta(|meta| {
+                if !meta.path.is_ident(key) {
+                    return skip(meta);
+                }
+                if meta.input.peek(Token![=]) {
+                    return func(meta.value()?.parse()?);
+                }
+                meta.parse_nested_meta(|meta| {
+                    if meta.path.is_ident("deserialize") {
+                        func(meta.value()?.parse()?)
+                    } else {
+                        skip(meta)
+                    }
+                })
+            })?;
+        }
+    }
+    Ok(())
+}
+
+/// Consumes an attribute argument which is not of interest
+fn skip(meta: ParseNestedMeta) -> syn::Result<()> {
+    if meta.input.peek(Token![=]) {
+        meta.value()?.parse::<Expr>()?;
+    } else if meta.input.peek(syn::token::Paren) {
+        meta.parse_nested_meta(skip)?;
+    }
+    Ok(())
+}
@@ -0,0 +1,69 @@
+use schemars::JsonSchema;
+use serde_json::json;
+use serde_json::Value;
+use swaggapi::as_responses::AsResponses;
+use swaggapi::internals::SchemaGenerator;
+use swaggapi::AsResponses;
+
+#[derive(JsonSchema)]
+#[allow(dead_code)]
+struct Conflict {
+    existing: u64,
+}
+
+#[derive(AsResponses)]
+#[allow(dead_code)]
+enum ApiError {
+    /// The requested resource
+    /// doesn't exist
+    #[response(status = 404)]
+    NotFound,
+
+    #[response(status = 409, description = "The resource already exists")]
+    Conflict(Conflict),
+
+    /// Ignored in favour of the explicit description
+    #[response(status = 409, description = "The resource has been modified")]
+    Modified(String),
+
+    #[response(status = 500)]
+    Internal,
+}
+
+fn responses<T: AsResponses>() -> Value {
+    let responses = SchemaGenerator::employ(&mut Default::default(), &mut Vec::new(), T::responses);
+    serde_json::to_value(responses).unwrap()
+}
+
+#[test]
+fn variants_with_the_same_status_are_combined() {
+    let responses = responses::<ApiError>();
+    assert_eq!(
+        responses["409"]["description"],
+        "The resource already exists\n\nThe resource has been modified"
+    );
+    assert_eq!(
+        responses["409"]["content"]["application/json"]["schema"]["oneOf"],
+        json!([
+            { "$ref": "#/components/schemas/Conflict" },
+            { "type": "string" },
+        ])
+    );
+}
+
+#[test]
+fn unit_variants_have_no_body() {
+    let responses = responses::<ApiError>();
+    assert_eq!(responses["404"].get("content"), None);
+    assert_eq!(responses["500"].get("content"), None);
+}
+
+#[test]
+fn descriptions_default_to_the_doc_comment() {
+    let responses = responses::<ApiError>();
+    assert_eq!(
+        responses["404"]["description"],
+        "The requested resource\ndoesn't exist"
+    );
+    assert_eq!(responses["500"]["description"], "Internal");
+}
@@ -0,0 +1,99 @@
+#![cfg(feature = "axum")]
+
+use axum::http::HeaderMap;
+use axum::http::StatusCode;
+use axum::Json;
+use serde_json::json;
+use serde_json::Value;
+use swaggapi::as_responses::AsResponses;
+use swaggapi::internals::SchemaGenerator;
+use swaggapi::utils::Accepted;
+use swaggapi::utils::Created;
+use swaggapi::utils::NoContent;
+use swaggapi::AsResponses;
+
+fn responses<T: AsResponses>() -> Value {
+    let responses = SchemaGenerator::employ(&mut Default::default(), &mut Vec::new(), T::responses);
+    serde_json::to_value(responses).unwrap()
+}
+
+#[test]
+fn leading_status_code_is_documented_as_default() {
+    let responses = responses::<(StatusCode, HeaderMap, Json<u64>)>();
+    let responses = responses.as_object().unwrap();
+    assert_eq!(responses.keys().collect::<Vec<_>>(), ["default"]);
+    assert_eq!(
+        responses["default"]["content"]["application/json"]["schema"],
+        json!({ "type": "integer", "format": "uint64", "minimum": 0 })
+    );
+    // A `HeaderMap`'s headers are only known at runtime
+    assert_eq!(responses["default"].get("headers"), None);
+}
+
+#[test]
+fn tuples_without_status_code_keep_the_status() {
+    let responses = responses::<(HeaderMap, Json<u64>)>();
+    let responses = responses.as_object().unwrap();
+    assert_eq!(responses.keys().collect::<Vec<_>>(), ["200"]);
+}
+
+#[cfg(feature = "axum-extra")]
+#[test]
+fn typed_headers_are_documented() {
+    use axum_extra::headers::ContentLocation;
+    use axum_extra::TypedHeader;
+
+    let responses = responses::<(StatusCode, TypedHeader<ContentLocation>, Json<u64>)>();
+    let header = &responses["default"]["headers"]["content-location"];
+    assert_eq!(header["required"], true);
+    assert_eq!(header["schema"], json!({ "type": "string" }));
+}
+
+#[test]
+fn status_wrappers_set_the_status_code() {
+    let created = responses::<Created<Json<u64>>>();
+    assert_eq!(
+        created.as_object().unwrap().keys().collect::<Vec<_>>(),
+        ["201"]
+    );
+    assert!(created["201"]["content"].get("application/json").is_some());
+
+    let accepted = responses::<Accepted<Json<u64>>>();
+    assert_eq!(
+        accepted.as_object().unwrap().keys().collect::<Vec<_>>(),
+        ["202"]
+    );
+
+    let no_content = responses::<NoContent>();
+    assert_eq!(
+        no_content.as_object().unwrap().keys().collect::<Vec<_>>(),
+        ["204"]
+    );
+    assert_eq!(no_content["204"].get("content"), None);
+}
+
+#[derive(AsResponses)]
+#[allow(dead_code)]
+enum Cached {
+    #[response(status = 200, description = "The cached value")]
+    Hit(String),
+
+    #[response(status = 404, description = "Nothing is cached")]
+    Miss,
+}
+
+#[test]
+fn result_merges_responses_with_the_same_status() {
+    let responses = responses::<Result<Json<u64>, Cached>>();
+    assert_eq!(
+        responses.as_object().unwrap().keys().collect::<Vec<_>>(),
+        ["200", "404"]
+    );
+    assert_eq!(
+        responses["200"]["content"]["application/json"]["schema"]["oneOf"],
+        json!([
+            { "type": "integer", "format": "uint64", "minimum": 0 },
+            { "type": "string" },
+        ])
+    );
+}
@@ -0,0 +1,57 @@
+#![cfg(feature = "axum")]
+
+use std::future::poll_fn;
+
+use axum::body::Body;
+use axum::http::Method;
+use axum::http::Request;
+use axum::http::StatusCode;
+use axum::Router;
+use swaggapi::ApiContext;
+use tower::Service;
+
+#[swaggapi::get("/users/{id}")]
+async fn get_user() -> &'static str {
+    "get"
+}
+
+#[swaggapi::post("/users/{id}")]
+async fn update_user() -> &'static str {
+    "post"
+}
+
+async fn request(router: &mut Router, method: Method) -> (StatusCode, String) {
+    poll_fn(|cx| Service::<Request<Body>>::poll_ready(router, cx))
+        .await
+        .unwrap();
+    let request = Request::builder()
+        .method(method)
+        .uri("/users/1")
+        .body(Body::empty())
+        .unwrap();
+    let response = router.call(request).await.unwrap();
+    let status = response.status();
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    (status, String::from_utf8(body.to_vec()).unwrap())
+}
+
+#[tokio::test]
+async fn methods_on_the_same_path_are_merged() {
+    let context: ApiContext<Router> = ApiContext::new().handler(get_user).handler(update_user);
+    let mut router = Router::from(context);
+
+    assert_eq!(
+        request(&mut router, Method::GET).await,
+        (StatusCode::OK, "get".to_string())
+    );
+    assert_eq!(
+        request(&mut router, Method::POST).await,
+        (StatusCode::OK, "post".to_string())
+    );
+    assert_eq!(
+        request(&mut router, Method::DELETE).await.0,
+        StatusCode::METHOD_NOT_ALLOWED
+    );
+}
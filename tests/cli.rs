@@ -0,0 +1,88 @@
+#![cfg(feature = "cli")]
+
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+use std::process::Command;
+use std::process::Output;
+
+use serde_json::json;
+
+fn swaggapi(args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_swaggapi"))
+        .args(args)
+        .output()
+        .unwrap()
+}
+
+fn write_spec(name: &str) -> PathBuf {
+    let directory = env::temp_dir().join(format!("swaggapi-bin-{}", std::process::id()));
+    fs::create_dir_all(&directory).unwrap();
+    let file = directory.join(name);
+    let document = json!({
+        "openapi": "3.1.0",
+        "info": { "title": "", "version": "" },
+        "paths": {
+            "/users/{id}": {
+                "get": { "operationId": "get_user", "responses": {} },
+            },
+        },
+    });
+    fs::write(&file, document.to_string()).unwrap();
+    file
+}
+
+#[test]
+fn reads_the_document_from_a_file() {
+    let file = write_spec("file.json");
+    let file = file.to_str().unwrap();
+
+    let output = swaggapi(&[file, "export", "--yaml"]);
+    assert!(output.status.success());
+    assert!(String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .any(|line| line == "openapi: 3.1.0"));
+
+    let output = swaggapi(&[file, "validate"]);
+    assert!(!output.status.success());
+    assert_eq!(
+        String::from_utf8_lossy(&output.stdout),
+        "#/paths/~1users~1{id}/get: Path parameter `id` is not declared\n"
+    );
+}
+
+#[test]
+fn reads_the_document_from_a_hook() {
+    let file = write_spec("hook.json");
+    let hook = format!(
+        "{} {} export",
+        env!("CARGO_BIN_EXE_swaggapi"),
+        file.display()
+    );
+
+    for command in [
+        ["diff", file.to_str().unwrap()],
+        ["breaking", file.to_str().unwrap()],
+    ] {
+        let output = swaggapi(&["--exec", &hook, command[0], command[1]]);
+        assert!(output.status.success(), "{output:?}");
+        assert_eq!(output.stdout, b"");
+    }
+
+    let hook = format!(
+        "{} {} unknown",
+        env!("CARGO_BIN_EXE_swaggapi"),
+        file.display()
+    );
+    let output = swaggapi(&["--exec", &hook, "validate"]);
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("failed"));
+}
+
+#[test]
+fn requires_a_command() {
+    assert!(!swaggapi(&[]).status.success());
+    assert!(!swaggapi(&["openapi.json"]).status.success());
+    assert!(!swaggapi(&["--exec", "true"]).status.success());
+    assert!(swaggapi(&["--help"]).status.success());
+}
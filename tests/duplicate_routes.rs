@@ -0,0 +1,120 @@
+#![cfg(feature = "axum")]
+
+use axum::Router;
+use swaggapi::internals::AccessSwaggapiPageBuilder;
+use swaggapi::internals::ContextHandler;
+use swaggapi::internals::SwaggapiPageBuilderImpl;
+use swaggapi::ApiContext;
+use swaggapi::DuplicateRouteBehavior;
+use swaggapi::PageOfEverything;
+use swaggapi::Registry;
+use swaggapi::RuntimePage;
+use swaggapi::SwaggapiPage;
+use swaggapi::SwaggapiPageBuilder;
+
+#[swaggapi::get("/users/{id}")]
+async fn by_id() -> String {
+    String::new()
+}
+
+#[swaggapi::get("/users/{user_id}")]
+async fn by_user_id() -> String {
+    String::new()
+}
+
+/// Adds both handlers directly because axum itself refuses to mount them together
+fn page(behavior: DuplicateRouteBehavior) -> RuntimePage {
+    let page = RuntimePage::new(SwaggapiPageBuilder::new().on_duplicate_route(behavior));
+    let builder = page.get_builder();
+    SwaggapiPageBuilderImpl::add_handler(&builder, &ContextHandler::new(by_id));
+    SwaggapiPageBuilderImpl::add_handler(&builder, &ContextHandler::new(by_user_id));
+    page
+}
+
+fn paths(page: &impl AccessSwaggapiPageBuilder) -> Vec<String> {
+    SwaggapiPageBuilderImpl::build(&page.get_builder())
+        .paths
+        .paths
+        .keys()
+        .cloned()
+        .collect()
+}
+
+#[test]
+fn differently_named_parameters_are_duplicates() {
+    let duplicates = page(DuplicateRouteBehavior::LastWins).duplicate_routes();
+    assert_eq!(duplicates.len(), 1);
+    assert_eq!(duplicates[0].path, "/users/{id}");
+    assert_eq!(duplicates[0].second_path, "/users/{user_id}");
+    assert!(duplicates[0].first.ends_with("::by_id"));
+    assert!(duplicates[0].second.ends_with("::by_user_id"));
+}
+
+#[test]
+fn last_wins_replaces_the_first_path() {
+    assert_eq!(
+        paths(&page(DuplicateRouteBehavior::LastWins)),
+        ["/users/{user_id}"]
+    );
+}
+
+#[test]
+fn first_wins_keeps_the_first_path() {
+    assert_eq!(
+        paths(&page(DuplicateRouteBehavior::FirstWins)),
+        ["/users/{id}"]
+    );
+}
+
+#[test]
+fn error_is_returned_by_validate_routes() {
+    assert_eq!(
+        page(DuplicateRouteBehavior::LastWins).validate_routes(),
+        Ok(())
+    );
+    let duplicates = page(DuplicateRouteBehavior::Error)
+        .validate_routes()
+        .unwrap_err();
+    assert_eq!(duplicates.len(), 1);
+}
+
+#[test]
+#[should_panic(expected = "has several handlers for the same route")]
+fn error_panics_when_requesting_the_document() {
+    page(DuplicateRouteBehavior::Error).openapi_json();
+}
+
+#[test]
+fn error_is_still_served() {
+    // This is what the `SwaggerUi` uses to serve the document
+    assert_eq!(
+        paths(&page(DuplicateRouteBehavior::Error)),
+        ["/users/{user_id}"]
+    );
+}
+
+#[test]
+fn adding_a_handler_again_is_no_duplicate() {
+    let page = RuntimePage::new(
+        SwaggapiPageBuilder::new().on_duplicate_route(DuplicateRouteBehavior::Error),
+    );
+    let builder = page.get_builder();
+    SwaggapiPageBuilderImpl::add_handler(&builder, &ContextHandler::new(by_id));
+    SwaggapiPageBuilderImpl::add_handler(&builder, &ContextHandler::new(by_id));
+    assert_eq!(page.duplicate_routes(), []);
+    assert_eq!(paths(&page), ["/users/{id}"]);
+}
+
+#[test]
+fn attaching_the_page_of_everything_is_no_duplicate() {
+    let registry = Registry::isolated();
+    let context: ApiContext<Router> = ApiContext::new()
+        .registry(registry.clone())
+        .page(PageOfEverything)
+        .handler(by_id);
+    let _ = Router::from(context);
+
+    let everything = registry.everything();
+    assert_eq!(everything.duplicate_routes(), []);
+    assert_eq!(paths(&everything), ["/users/{id}"]);
+}
@@ -0,0 +1,53 @@
+#![cfg(feature = "axum")]
+
+use axum::Router;
+use schemars::JsonSchema;
+use serde_json::json;
+use swaggapi::headers::TypedHeaders;
+use swaggapi::ApiContext;
+use swaggapi::FromHeaders;
+
+#[derive(FromHeaders, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+#[allow(dead_code)]
+struct Client {
+    request_id: String,
+
+    #[serde(rename = "size")]
+    page_size: Option<u32>,
+
+    #[schemars(rename = "theme")]
+    #[cookie]
+    r#type: Option<String>,
+}
+
+#[swaggapi::get("/client")]
+async fn client(_client: TypedHeaders<Client>) -> String {
+    String::new()
+}
+
+#[test]
+fn renamed_fields_are_documented() {
+    let context: ApiContext<Router> = ApiContext::new().handler(client);
+    let openapi = serde_json::to_value(&*context.openapi()).unwrap();
+    let parameters = openapi["paths"]["/client"]["get"]["parameters"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|parameter| {
+            json!({
+                "in": parameter["in"],
+                "name": parameter["name"],
+                "required": parameter["required"] == true,
+            })
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(
+        parameters,
+        [
+            json!({ "in": "header", "name": "request-id", "required": true }),
+            json!({ "in": "header", "name": "page-size", "required": false }),
+            json!({ "in": "cookie", "name": "type", "required": false }),
+        ]
+    );
+}
@@ -0,0 +1,88 @@
+#![cfg(feature = "actix-multipart")]
+
+use actix_web::http::header::CONTENT_TYPE;
+use actix_web::http::StatusCode;
+use actix_web::test::TestRequest;
+use actix_web::FromRequest;
+use serde_json::json;
+use swaggapi::multipart::FileUpload;
+use swaggapi::multipart::MultipartConfig;
+use swaggapi::multipart::TypedMultipart;
+use swaggapi::ApiContext;
+use swaggapi::FromMultipart;
+use swaggapi::PageOfEverything;
+use swaggapi::SwaggapiPage;
+
+#[derive(FromMultipart)]
+#[serde(rename_all = "camelCase")]
+#[allow(dead_code)]
+struct Upload {
+    /// The upload's title
+    ///
+    /// Shown in the list of uploads
+    display_title: String,
+
+    #[serde(rename = "attachment")]
+    file: Option<FileUpload>,
+
+    r#type: Option<String>,
+}
+
+#[swaggapi::post("/upload")]
+async fn upload(upload: TypedMultipart<Upload>) -> String {
+    upload.0.display_title
+}
+
+#[test]
+fn parts_are_named_like_serde() {
+    let page = ApiContext::new("")
+        .handler(upload)
+        .build_page(PageOfEverything);
+    let openapi = serde_json::to_value(&*page.openapi()).unwrap();
+    let schema = &openapi["paths"]["/upload"]["post"]["requestBody"]["content"]
+        ["multipart/form-data"]["schema"];
+    assert_eq!(
+        schema["properties"],
+        json!({
+            "displayTitle": {
+                "type": "string",
+                "description": "The upload's title\n\nShown in the list of uploads",
+            },
+            "attachment": { "type": "string", "format": "binary" },
+            "type": { "type": "string" },
+        })
+    );
+    assert_eq!(schema["required"], json!(["displayTitle"]));
+}
+
+fn request(title: &str) -> TestRequest {
+    TestRequest::post()
+        .insert_header((CONTENT_TYPE, "multipart/form-data; boundary=boundary"))
+        .set_payload(format!(
+            "--boundary\r\n\
+            Content-Disposition: form-data; name=\"displayTitle\"\r\n\
+            \r\n\
+            {title}\r\n\
+            --boundary--\r\n"
+        ))
+}
+
+#[actix_web::test]
+async fn parts_are_limited() {
+    let (req, mut payload) = request("short")
+        .app_data(MultipartConfig::default().limit(8))
+        .to_http_parts();
+    let extracted = TypedMultipart::<Upload>::from_request(&req, &mut payload).await;
+    assert_eq!(extracted.unwrap().0.display_title, "short");
+
+    let (req, mut payload) = request("too long")
+        .app_data(MultipartConfig::default().limit(4))
+        .to_http_parts();
+    let Err(error) = TypedMultipart::<Upload>::from_request(&req, &mut payload).await else {
+        panic!("The limit should be exceeded");
+    };
+    assert_eq!(
+        error.as_response_error().status_code(),
+        StatusCode::PAYLOAD_TOO_LARGE
+    );
+}
@@ -0,0 +1,107 @@
+#![cfg(feature = "axum")]
+
+use axum::Router;
+use swaggapi::diagnostics::DuplicateOperationId;
+use swaggapi::ApiContext;
+use swaggapi::RuntimePage;
+use swaggapi::SwaggapiPage;
+use swaggapi::SwaggapiPageBuilder;
+
+mod a {
+    #[swaggapi::get("/a")]
+    pub async fn get() -> String {
+        String::new()
+    }
+}
+
+mod b {
+    #[swaggapi::get("/b")]
+    pub async fn get() -> String {
+        String::new()
+    }
+}
+
+#[swaggapi::get("/explicit", operation_id = "get")]
+async fn explicit() -> String {
+    String::new()
+}
+
+#[swaggapi::get("/first", operation_id = "shared")]
+async fn first() -> String {
+    String::new()
+}
+
+#[swaggapi::get("/second", operation_id = "shared")]
+async fn second() -> String {
+    String::new()
+}
+
+fn operation_id(context: &ApiContext<Router>, path: &str) -> String {
+    let openapi = serde_json::to_value(&*context.openapi()).unwrap();
+    openapi["paths"][path]["get"]["operationId"]
+        .as_str()
+        .unwrap()
+        .to_string()
+}
+
+#[test]
+fn renaming_does_not_depend_on_registration_order() {
+    for context in [
+        ApiContext::new().handler(a::get).handler(b::get),
+        ApiContext::new().handler(b::get).handler(a::get),
+    ] {
+        assert_eq!(operation_id(&context, "/a"), "get");
+        assert_eq!(operation_id(&context, "/b"), "get_2");
+    }
+}
+
+#[test]
+fn renamed_operations_are_reported() {
+    let context: ApiContext<Router> = ApiContext::new().handler(b::get).handler(a::get);
+    let page = context.build_page(swaggapi::PageOfEverything);
+    assert_eq!(
+        page.duplicate_operation_ids(),
+        [DuplicateOperationId {
+            operation: "GET /b".to_string(),
+            operation_id: "get".to_string(),
+            renamed: "get_2".to_string(),
+            explicit: false,
+        }]
+    );
+}
+
+#[test]
+fn explicit_operation_ids_are_kept() {
+    let context: ApiContext<Router> = ApiContext::new().handler(a::get).handler(explicit);
+    assert_eq!(operation_id(&context, "/explicit"), "get");
+    assert_eq!(operation_id(&context, "/a"), "get_2");
+}
+
+#[test]
+fn colliding_explicit_operation_ids_are_reported() {
+    let context: ApiContext<Router> = ApiContext::new().handler(second).handler(first);
+    assert_eq!(operation_id(&context, "/first"), "shared");
+    assert_eq!(operation_id(&context, "/second"), "shared_2");
+    assert_eq!(
+        context
+            .build_page(swaggapi::PageOfEverything)
+            .duplicate_operation_ids(),
+        [DuplicateOperationId {
+            operation: "GET /second".to_string(),
+            operation_id: "shared".to_string(),
+            renamed: "shared_2".to_string(),
+            explicit: true,
+        }]
+    );
+}
+
+#[test]
+#[should_panic(expected = "several handlers with the same explicit operationId")]
+fn colliding_explicit_operation_ids_fail_strict_pages() {
+    let page = RuntimePage::new(SwaggapiPageBuilder::new().strict(true));
+    let context: ApiContext<Router> = ApiContext::new()
+        .page(page.clone())
+        .handler(first)
+        .handler(second);
+    context.build_page(page).openapi();
+}
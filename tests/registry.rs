@@ -0,0 +1,52 @@
+#![cfg(feature = "axum")]
+
+use axum::Router;
+use swaggapi::ApiContext;
+use swaggapi::PageOfEverything;
+use swaggapi::Registry;
+use swaggapi::SwaggapiPage;
+use swaggapi::SwaggapiPageBuilder;
+
+static PAGE: SwaggapiPageBuilder = SwaggapiPageBuilder::new().title("Isolated");
+
+#[swaggapi::get("/first")]
+async fn first() -> String {
+    String::new()
+}
+
+#[swaggapi::get("/second")]
+async fn second() -> String {
+    String::new()
+}
+
+fn paths(page: impl SwaggapiPage) -> Vec<String> {
+    page.openapi().paths.paths.keys().cloned().collect()
+}
+
+#[test]
+fn isolated_registries_are_separate() {
+    let one = Registry::isolated();
+    let two = Registry::isolated();
+    let _ = Router::from(
+        ApiContext::<Router>::new()
+            .registry(one.clone())
+            .page(&PAGE)
+            .handler(first),
+    );
+    let _ = Router::from(
+        ApiContext::<Router>::new()
+            .registry(two.clone())
+            .page(&PAGE)
+            .handler(second),
+    );
+
+    assert_eq!(paths(one.page(&PAGE)), ["/first"]);
+    assert_eq!(paths(one.everything()), ["/first"]);
+    assert_eq!(paths(two.page(&PAGE)), ["/second"]);
+    assert_eq!(paths(two.everything()), ["/second"]);
+
+    // The copies keep the original's configuration while the original stays empty
+    assert_eq!(one.page(&PAGE).openapi().info.title, "Isolated");
+    assert_eq!(paths(&PAGE), Vec::<String>::new());
+    assert!(!paths(PageOfEverything).contains(&"/first".to_string()));
+}
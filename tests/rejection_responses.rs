@@ -0,0 +1,37 @@
+#![cfg(feature = "axum")]
+
+use axum::Json;
+use swaggapi::internals::AccessSwaggapiPageBuilder;
+use swaggapi::internals::ContextHandler;
+use swaggapi::internals::SwaggapiPageBuilderImpl;
+use swaggapi::RuntimePage;
+use swaggapi::SwaggapiPage;
+use swaggapi::SwaggapiPageBuilder;
+
+#[swaggapi::post("/echo")]
+async fn echo(Json(body): Json<String>) -> String {
+    body
+}
+
+fn status_codes(builder: SwaggapiPageBuilder) -> Vec<String> {
+    let page = RuntimePage::new(builder);
+    SwaggapiPageBuilderImpl::add_handler(&page.get_builder(), &ContextHandler::new(echo));
+    let openapi = serde_json::to_value(&*page.openapi()).unwrap();
+    openapi["paths"]["/echo"]["post"]["responses"]
+        .as_object()
+        .unwrap()
+        .keys()
+        .cloned()
+        .collect()
+}
+
+#[test]
+fn rejection_responses_are_disabled_by_default() {
+    assert_eq!(status_codes(SwaggapiPageBuilder::new()), ["200"]);
+}
+
+#[test]
+fn rejection_responses_can_be_enabled() {
+    let codes = status_codes(SwaggapiPageBuilder::new().rejection_responses(true));
+    assert_eq!(codes, ["200", "400", "415", "422"]);
+}
@@ -0,0 +1,53 @@
+#![cfg(feature = "actix")]
+
+use actix_web::web::Form;
+use actix_web::web::Json;
+use actix_web::Either;
+use swaggapi::diagnostics::ConflictingRequestBodies;
+use swaggapi::ApiContext;
+use swaggapi::PageOfEverything;
+use swaggapi::RuntimePage;
+use swaggapi::SwaggapiPage;
+use swaggapi::SwaggapiPageBuilder;
+
+#[swaggapi::post("/conflicting")]
+async fn conflicting(_first: Json<String>, _second: Json<u32>) -> String {
+    String::new()
+}
+
+#[swaggapi::post("/alternatives")]
+async fn alternatives(_body: Either<Json<String>, Form<Vec<(String, String)>>>) -> String {
+    String::new()
+}
+
+#[test]
+fn conflicting_bodies_are_reported() {
+    let context = ApiContext::new("").handler(conflicting);
+    let page = context.build_page(PageOfEverything);
+    assert_eq!(
+        page.conflicting_request_bodies(),
+        [ConflictingRequestBodies {
+            handler: format!("{}::conflicting", module_path!()),
+            arguments: vec![1, 2],
+        }]
+    );
+
+    // The handler is still documented
+    let openapi = page.openapi();
+    assert!(openapi.paths.paths.contains_key("/conflicting"));
+}
+
+#[test]
+#[should_panic(expected = "has handlers with several arguments consuming the request body")]
+fn conflicting_bodies_fail_strict_pages() {
+    let page = RuntimePage::new(SwaggapiPageBuilder::new().strict(true));
+    let context = ApiContext::new("").page(page.clone()).handler(conflicting);
+    context.build_page(page).openapi();
+}
+
+#[test]
+fn alternative_bodies_are_no_conflict() {
+    let context = ApiContext::new("").handler(alternatives);
+    let page = context.build_page(PageOfEverything);
+    assert_eq!(page.conflicting_request_bodies(), []);
+}
@@ -0,0 +1,74 @@
+#![cfg(feature = "axum")]
+
+use axum::extract::FromRequestParts;
+use axum::http::request::Parts;
+use axum::Router;
+use swaggapi::handler_argument::HandlerArgument;
+use swaggapi::handler_argument::ShouldBeHandlerArgument;
+use swaggapi::security::SecurityScheme;
+use swaggapi::ApiContext;
+
+macro_rules! authenticating_extractor {
+    ($ty:ident, $scheme:literal) => {
+        struct $ty;
+        impl<S: Send + Sync> FromRequestParts<S> for $ty {
+            type Rejection = ();
+            async fn from_request_parts(_: &mut Parts, _: &S) -> Result<Self, ()> {
+                Ok(Self)
+            }
+        }
+        impl ShouldBeHandlerArgument for $ty {}
+        impl HandlerArgument for $ty {
+            fn security_schemes() -> Vec<(&'static str, SecurityScheme)> {
+                vec![($scheme, SecurityScheme::bearer())]
+            }
+        }
+    };
+}
+authenticating_extractor!(Session, "session");
+authenticating_extractor!(Token, "token");
+
+#[swaggapi::get("/single")]
+async fn single(_session: Session) -> String {
+    String::new()
+}
+
+#[swaggapi::get("/both")]
+async fn both(_session: Session, _token: Token) -> String {
+    String::new()
+}
+
+#[swaggapi::get("/alternatives", security("basic", "key"))]
+async fn alternatives(_session: Session) -> String {
+    String::new()
+}
+
+fn security(handler: &str) -> serde_json::Value {
+    let context: ApiContext<Router> = ApiContext::new()
+        .handler(single)
+        .handler(both)
+        .handler(alternatives);
+    let openapi = serde_json::to_value(&*context.openapi()).unwrap();
+    openapi["paths"][handler]["get"]["security"].clone()
+}
+
+#[test]
+fn extractor_scheme_is_a_single_requirement() {
+    assert_eq!(security("/single"), serde_json::json!([{"session": []}]));
+}
+
+#[test]
+fn several_extractor_schemes_are_all_required() {
+    assert_eq!(
+        security("/both"),
+        serde_json::json!([{"session": [], "token": []}])
+    );
+}
+
+#[test]
+fn extractor_schemes_are_added_to_every_alternative() {
+    assert_eq!(
+        security("/alternatives"),
+        serde_json::json!([{"basic": [], "session": []}, {"key": [], "session": []}])
+    );
+}
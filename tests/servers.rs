@@ -0,0 +1,115 @@
+#![cfg(feature = "axum")]
+
+use std::future::poll_fn;
+
+use axum::body::Body;
+use axum::http::Request;
+use axum::Router;
+use serde_json::json;
+use serde_json::Value;
+use swaggapi::servers::Server;
+use swaggapi::servers::ServerVariable;
+use swaggapi::SwaggapiPageBuilder;
+use swaggapi::SwaggerUi;
+use tower::Service;
+
+static PAGE: SwaggapiPageBuilder = SwaggapiPageBuilder::new()
+    .filename("servers.json")
+    .servers(&[Server::new("https://{region}.example.com/v1")
+        .description("Production")
+        .variables(&[(
+            "region",
+            ServerVariable::new("eu")
+                .enumeration(&["eu", "us"])
+                .description("The region"),
+        )])]);
+
+/// Requests the page's document from a `SwaggerUi` and returns its servers
+async fn servers(servers_from_request: bool, headers: &[(&str, &str)]) -> Value {
+    let mut router: Router = SwaggerUi::without_everything()
+        .page("Servers", &PAGE)
+        .servers_from_request(servers_from_request)
+        .into();
+    poll_fn(|cx| Service::<Request<Body>>::poll_ready(&mut router, cx))
+        .await
+        .unwrap();
+    let mut request = Request::builder().uri("/swagger-ui/servers.json");
+    for (name, value) in headers {
+        request = request.header(*name, *value);
+    }
+    let response = router
+        .call(request.body(Body::empty()).unwrap())
+        .await
+        .unwrap();
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    serde_json::from_slice::<Value>(&body).unwrap()["servers"].take()
+}
+
+/// The url of the first server
+async fn request_server(headers: &[(&str, &str)]) -> Value {
+    servers(true, headers).await[0]["url"].take()
+}
+
+#[tokio::test]
+async fn declared_servers_are_documented() {
+    let expected = json!([{
+        "url": "https://{region}.example.com/v1",
+        "description": "Production",
+        "variables": {
+            "region": {
+                "enum": ["eu", "us"],
+                "default": "eu",
+                "description": "The region",
+            },
+        },
+    }]);
+    assert_eq!(servers(false, &[("host", "localhost")]).await, expected);
+}
+
+#[tokio::test]
+async fn request_server_is_inserted_first() {
+    let servers = servers(true, &[("host", "localhost:8080")]).await;
+    assert_eq!(servers[0], json!({ "url": "http://localhost:8080" }));
+    assert_eq!(servers[1]["url"], "https://{region}.example.com/v1");
+}
+
+#[tokio::test]
+async fn forwarded_headers_take_precedence_over_host() {
+    assert_eq!(
+        request_server(&[
+            ("host", "localhost:8080"),
+            ("x-forwarded-host", "api.example.com"),
+            ("x-forwarded-proto", "https"),
+            ("x-forwarded-prefix", "/v1"),
+        ])
+        .await,
+        "https://api.example.com/v1"
+    );
+}
+
+#[tokio::test]
+async fn first_forwarded_value_is_used() {
+    assert_eq!(
+        request_server(&[
+            ("x-forwarded-host", "api.example.com, proxy.internal"),
+            ("x-forwarded-proto", "https,http"),
+        ])
+        .await,
+        "https://api.example.com"
+    );
+}
+
+#[tokio::test]
+async fn trailing_slash_of_the_prefix_is_removed() {
+    assert_eq!(
+        request_server(&[("host", "localhost"), ("x-forwarded-prefix", "/api/")]).await,
+        "http://localhost/api"
+    );
+}
+
+#[tokio::test]
+async fn missing_host_adds_no_server() {
+    assert_eq!(servers(true, &[]).await.as_array().unwrap().len(), 1);
+}
@@ -0,0 +1,42 @@
+#![cfg(feature = "axum")]
+
+use axum::extract::FromRef;
+use axum::extract::State;
+use axum::Router;
+use swaggapi::ApiContext;
+
+#[derive(Clone)]
+struct AppState {
+    sub: SubState,
+}
+
+#[derive(Clone)]
+struct SubState;
+
+impl FromRef<AppState> for SubState {
+    fn from_ref(state: &AppState) -> Self {
+        state.sub.clone()
+    }
+}
+
+#[swaggapi::get("/inferred")]
+async fn inferred(State(_): State<SubState>) -> String {
+    String::new()
+}
+
+#[swaggapi::get("/explicit", state = AppState)]
+async fn explicit(State(_): State<SubState>) -> String {
+    String::new()
+}
+
+#[test]
+#[should_panic(expected = "requires a different state")]
+fn sub_state_requires_explicit_state() {
+    let _: ApiContext<Router<AppState>> = ApiContext::new().handler(inferred);
+}
+
+#[test]
+fn explicit_state_accepts_sub_state() {
+    let context: ApiContext<Router<AppState>> = ApiContext::default().handler(explicit);
+    let _: Router = Router::from(context).with_state(AppState { sub: SubState });
+}
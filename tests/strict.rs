@@ -0,0 +1,68 @@
+use schemars::gen::SchemaGenerator;
+use schemars::schema::InstanceType;
+use schemars::schema::ObjectValidation;
+use schemars::schema::Schema;
+use schemars::schema::SchemaObject;
+use schemars::JsonSchema;
+use swaggapi::internals::AccessSwaggapiPageBuilder;
+use swaggapi::internals::SwaggapiPageBuilderImpl;
+use swaggapi::RuntimePage;
+use swaggapi::SwaggapiPage;
+use swaggapi::SwaggapiPageBuilder;
+
+/// Uses `patternProperties` which OpenAPI 3.0 doesn't support
+struct Pair;
+impl JsonSchema for Pair {
+    fn schema_name() -> String {
+        "Pair".to_string()
+    }
+
+    fn json_schema(_: &mut SchemaGenerator) -> Schema {
+        Schema::Object(SchemaObject {
+            instance_type: Some(InstanceType::Object.into()),
+            object: Some(Box::new(ObjectValidation {
+                pattern_properties: [("^x-".to_string(), Schema::Bool(true))]
+                    .into_iter()
+                    .collect(),
+                ..Default::default()
+            })),
+            ..Default::default()
+        })
+    }
+}
+
+#[derive(JsonSchema)]
+#[allow(dead_code)]
+struct Plain {
+    number: u32,
+}
+
+fn page<T: JsonSchema>(strict: bool) -> RuntimePage {
+    let page = RuntimePage::new(SwaggapiPageBuilder::new().strict(strict));
+    page.add_schema::<T>();
+    page
+}
+
+#[test]
+fn validate_returns_diagnostics() {
+    let diagnostics = page::<Pair>(false).validate().unwrap_err();
+    assert!(!diagnostics.is_empty());
+    assert!(diagnostics.iter().all(|diagnostic| diagnostic.schema == "Pair"));
+}
+
+#[test]
+fn validate_accepts_lossless_schemas() {
+    assert_eq!(page::<Plain>(true).validate(), Ok(()));
+}
+
+#[test]
+#[should_panic(expected = "lost information converting its schemas")]
+fn strict_page_panics() {
+    page::<Pair>(true).openapi();
+}
+
+#[test]
+fn strict_page_is_still_served() {
+    // This is what the `SwaggerUi` uses to serve the document
+    SwaggapiPageBuilderImpl::build_json(&page::<Pair>(true).get_builder());
+}
@@ -0,0 +1,87 @@
+#![cfg(feature = "axum")]
+
+use axum::Router;
+use serde_json::json;
+use swaggapi::tags::Tag;
+use swaggapi::tags::TagGroup;
+use swaggapi::ApiContext;
+use swaggapi::Registry;
+use swaggapi::SwaggapiPage;
+use swaggapi::SwaggapiPageBuilder;
+
+static PAGE: SwaggapiPageBuilder = SwaggapiPageBuilder::new()
+    .tags(&[
+        Tag::new("users").description("Everything about your users"),
+        Tag::new("admin").description("Endpoints only available to admins"),
+    ])
+    .tag_groups(&[
+        TagGroup::new("Accounts", &["users", "admin"]),
+        TagGroup::new("Shop", &["items", "orders"]),
+    ]);
+
+#[swaggapi::get("/items")]
+async fn get_items() -> String {
+    String::new()
+}
+
+#[swaggapi::get("/orders")]
+async fn get_orders() -> String {
+    String::new()
+}
+
+#[swaggapi::get("/admin")]
+async fn get_admin() -> String {
+    String::new()
+}
+
+#[test]
+fn page_tags_precede_the_contexts_ones() {
+    let registry = Registry::isolated();
+    let items: ApiContext<Router> = ApiContext::new()
+        .registry(registry.clone())
+        .page(&PAGE)
+        .tag_definition(Tag::new("items").description("The items for sale"))
+        .handler(get_items);
+    let orders: ApiContext<Router> = ApiContext::new()
+        .registry(registry.clone())
+        .page(&PAGE)
+        .tag_definition(Tag::new("orders"))
+        .tag_definition(Tag::new("items").description("Ignored in favour of the first"))
+        .handler(get_orders);
+    let admin: ApiContext<Router> = ApiContext::new()
+        .registry(registry.clone())
+        .page(&PAGE)
+        .tag_definition(Tag::new("admin").description("Ignored in favour of the page's"))
+        .handler(get_admin);
+    for context in [items, orders, admin] {
+        let _ = Router::from(context);
+    }
+
+    let openapi = serde_json::to_value(&*registry.page(&PAGE).openapi()).unwrap();
+    assert_eq!(
+        openapi["tags"],
+        json!([
+            { "name": "users", "description": "Everything about your users" },
+            { "name": "admin", "description": "Endpoints only available to admins" },
+            { "name": "items", "description": "The items for sale" },
+            { "name": "orders" },
+        ])
+    );
+    assert_eq!(
+        openapi["x-tagGroups"],
+        json!([
+            { "name": "Accounts", "tags": ["users", "admin"] },
+            { "name": "Shop", "tags": ["items", "orders"] },
+        ])
+    );
+}
+
+#[test]
+fn tag_groups_are_omitted_if_empty() {
+    let openapi = serde_json::to_value(&*Registry::isolated().page(&PAGE).openapi()).unwrap();
+    assert!(openapi.get("x-tagGroups").is_some());
+
+    static EMPTY: SwaggapiPageBuilder = SwaggapiPageBuilder::new();
+    let openapi = serde_json::to_value(&*Registry::isolated().page(&EMPTY).openapi()).unwrap();
+    assert_eq!(openapi.get("x-tagGroups"), None);
+}